@@ -0,0 +1,44 @@
+//! Solves the days with a `parallel` path against large generated inputs.
+//! Save a serial baseline and compare the parallel build against it:
+//!
+//! ```text
+//! cargo bench -p aoc --bench solve -- --save-baseline serial
+//! cargo bench -p aoc --bench solve --features parallel -- --baseline serial
+//! ```
+
+use common::{Answer, Rng};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Seed for every generated input, so runs are comparable
+const SEED: u64 = 2023;
+
+fn bench_part(c: &mut Criterion, name: &str, input: &str, part: impl Fn(&str) -> Answer) {
+    c.bench_function(name, |b| b.iter(|| part(std::hint::black_box(input))));
+}
+
+fn solve(c: &mut Criterion) {
+    let day1 = day1::generate(&mut Rng::new(SEED), 200_000);
+    bench_part(c, "day1 part1", &day1, day1::part1);
+    bench_part(c, "day1 part2", &day1, day1::part2);
+
+    let day2 = day2::generate(&mut Rng::new(SEED), 100_000);
+    bench_part(c, "day2 part1", &day2, day2::part1);
+    bench_part(c, "day2 part2", &day2, day2::part2);
+
+    let day4 = day4::generate(&mut Rng::new(SEED), 100_000);
+    bench_part(c, "day4 part1", &day4, day4::part1);
+
+    let day5 = day5::generate(&mut Rng::new(SEED), 2_000);
+    bench_part(c, "day5 part1", &day5, day5::part1);
+
+    let day7 = day7::generate(&mut Rng::new(SEED), 100_000);
+    bench_part(c, "day7 part1", &day7, day7::part1);
+    bench_part(c, "day7 part2", &day7, day7::part2);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = solve
+}
+criterion_main!(benches);
@@ -0,0 +1,175 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use toml::{Table, Value};
+
+use crate::days::BoxError;
+
+/// Answer recorded for one part of a day against a named input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownAnswer {
+    pub day: u8,
+    pub input: String,
+    pub part: u8,
+    pub answer: String,
+    pub path: PathBuf,
+}
+
+/// Read every known answer from a registry file, along with the answers
+/// from each registered day's examples manifest. The registry looks like:
+///
+/// ```toml
+/// [day1.input]
+/// part1 = 54877
+/// part2 = 54100
+/// ```
+pub fn load(path: &Path) -> Result<Vec<KnownAnswer>, BoxError> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+
+    let root = path.parent().unwrap_or(Path::new("."));
+    let mut answers = parse(root, &text).map_err(|err| format!("{}: {err}", path.display()))?;
+
+    for day in crate::days::DAYS {
+        for example in examples::load(&examples_dir(root, day))? {
+            for part in [1, 2] {
+                if let Some(answer) = example.answer(part) {
+                    answers.push(KnownAnswer {
+                        day,
+                        input: example.name.clone(),
+                        part,
+                        answer: answer.to_string(),
+                        path: example.path.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    answers.sort_by_key(|known| (known.day, known.input.clone(), known.part));
+    Ok(answers)
+}
+
+/// Directory holding a day's examples and their manifest
+pub fn examples_dir(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("day{day}")).join("examples")
+}
+
+/// Inputs in the registry are `dayN/<name>.txt` next to it
+fn parse(root: &Path, text: &str) -> Result<Vec<KnownAnswer>, String> {
+    let registry = text.parse::<Table>().map_err(|err| err.to_string())?;
+    let mut answers = vec![];
+
+    for (day_key, inputs) in &registry {
+        let day = day_key
+            .strip_prefix("day")
+            .and_then(|day| day.parse::<u8>().ok())
+            .ok_or(format!("expected a key like `day1`, found `{day_key}`"))?;
+        let inputs = inputs
+            .as_table()
+            .ok_or(format!("expected `{day_key}` to be a table of inputs"))?;
+
+        for (input, parts) in inputs {
+            let parts = parts.as_table().ok_or(format!(
+                "expected `{day_key}.{input}` to be a table of parts"
+            ))?;
+
+            for (part_key, answer) in parts {
+                let part = match part_key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => {
+                        return Err(format!(
+                            "expected `part1` or `part2` in `{day_key}.{input}`, found `{part_key}`"
+                        ))
+                    }
+                };
+                let answer = match answer {
+                    Value::Integer(answer) => answer.to_string(),
+                    Value::String(answer) => answer.clone(),
+                    _ => {
+                        return Err(format!(
+                            "expected `{day_key}.{input}.{part_key}` to be a number or string"
+                        ))
+                    }
+                };
+
+                answers.push(KnownAnswer {
+                    day,
+                    input: input.clone(),
+                    part,
+                    answer,
+                    path: root.join(format!("day{day}")).join(format!("{input}.txt")),
+                });
+            }
+        }
+    }
+
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let answers = parse(
+            Path::new("aoc"),
+            "[day2.example]\npart1 = 8\n\n[day2.input]\npart2 = \"ABC\"\npart1 = 12\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            answers,
+            vec![
+                KnownAnswer {
+                    day: 2,
+                    input: "example".to_string(),
+                    part: 1,
+                    answer: "8".to_string(),
+                    path: PathBuf::from("aoc/day2/example.txt"),
+                },
+                KnownAnswer {
+                    day: 2,
+                    input: "input".to_string(),
+                    part: 1,
+                    answer: "12".to_string(),
+                    path: PathBuf::from("aoc/day2/input.txt"),
+                },
+                KnownAnswer {
+                    day: 2,
+                    input: "input".to_string(),
+                    part: 2,
+                    answer: "ABC".to_string(),
+                    path: PathBuf::from("aoc/day2/input.txt"),
+                },
+            ]
+        );
+        assert_eq!(
+            parse(Path::new("."), "[day1.input]\npart3 = 1\n"),
+            Err("expected `part1` or `part2` in `day1.input`, found `part3`".to_string())
+        );
+    }
+
+    #[test]
+    fn registry_matches_solutions_test() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.toml");
+
+        for known in load(&path).unwrap() {
+            let input = fs::read_to_string(&known.path).unwrap();
+            let answer = crate::days::solve(known.day, &input, known.part).unwrap();
+
+            assert_eq!(
+                answer.to_string(),
+                known.answer,
+                "day {} part {} against {}",
+                known.day,
+                known.part,
+                known.input
+            );
+        }
+    }
+}
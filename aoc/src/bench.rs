@@ -0,0 +1,358 @@
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use common::{Answer, Variant};
+
+use crate::days::{self, BoxError};
+
+/// Timings from one benchmark run, as kept in the history file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// Seconds since the unix epoch when the run finished
+    pub timestamp: u64,
+    /// Whether the runner was built with the `parallel` feature
+    pub parallel: bool,
+    pub timings: Vec<Timing>,
+}
+
+/// Median time of one phase of a day against one input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: u8,
+    pub input: String,
+    /// `parse`, `part1` or `part2`
+    pub phase: String,
+    pub nanos: u64,
+}
+
+/// How one phase's time moved between two recorded runs
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub day: u8,
+    pub input: String,
+    pub phase: String,
+    /// `None` when the previous run didn't time this phase
+    pub previous: Option<Duration>,
+    pub latest: Duration,
+}
+
+impl Change {
+    /// Change in time as a percentage of the previous time
+    pub fn percent(&self) -> Option<f64> {
+        let previous = self.previous?.as_secs_f64();
+        (previous > 0.0).then(|| (self.latest.as_secs_f64() - previous) / previous * 100.0)
+    }
+
+    /// Whether the phase got slower by more than `threshold` percent
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent().is_some_and(|percent| percent > threshold)
+    }
+}
+
+/// Size of the generated input timed for `day`, large enough to take a
+/// while without taking minutes. Sizes mean different things for each
+/// day, see `gen`
+pub fn generated_size(day: u8) -> usize {
+    match day {
+        // the schematic is size by size
+        3 => 500,
+        5 => 1_000,
+        // both parts are closed form, so only the size of the numbers
+        // matters. The long race of more than ten races needs `bigint`
+        6 if cfg!(feature = "bigint") => 10_000,
+        6 => 10,
+        // the network has at most 26^3 nodes
+        8 => 10_000,
+        _ => 100_000,
+    }
+}
+
+/// Median times of one day against one input
+pub struct Measured {
+    /// Time of each phase that ran, in order
+    pub timings: Vec<(&'static str, Duration)>,
+    /// Why a part is missing from the timings, when it failed
+    pub error: Option<BoxError>,
+}
+
+/// Median time of parsing and each part of `day` against `input`, over
+/// `runs` runs. A part without a solution is left out, as is one that fails
+pub fn measure(day: u8, input: &str, runs: usize) -> Result<Measured, BoxError> {
+    let mut parse_times = vec![];
+    let mut part_times = [vec![], vec![]];
+    let mut error = None;
+
+    for _ in 0..runs.max(1) {
+        let day_run =
+            days::run(day, input, &[1, 2]).ok_or(format!("day {day} has no solution"))??;
+        parse_times.push(day_run.parse_time);
+
+        for part_run in day_run.parts {
+            match part_run.answer {
+                Ok(Some(_)) => part_times[usize::from(part_run.part) - 1].push(part_run.time),
+                Ok(None) => {}
+                Err(err) => error = Some(err),
+            }
+        }
+    }
+
+    let mut timings = vec![("parse", median(parse_times))];
+    for (phase, times) in ["part1", "part2"].into_iter().zip(part_times) {
+        if !times.is_empty() {
+            timings.push((phase, median(times)));
+        }
+    }
+
+    Ok(Measured { timings, error })
+}
+
+/// Answer and median time of one variant of a part
+pub struct VariantRun {
+    pub part: u8,
+    pub name: &'static str,
+    pub answer: Result<Answer, BoxError>,
+    pub time: Duration,
+    /// Why the variant didn't run, being too slow for the input. Its
+    /// answer is then an error and its time zero
+    pub skipped: Option<String>,
+}
+
+/// Answer and median time, over `runs` runs, of each of a day's
+/// `variants` against `input`. Each variant parses the input itself, so
+/// parsing is part of its time
+pub fn measure_variants<E: Into<BoxError>>(
+    variants: &[Variant<E>],
+    input: &str,
+    runs: usize,
+) -> Vec<VariantRun> {
+    variants
+        .iter()
+        .map(|variant| {
+            if let Some(reason) = (variant.too_slow)(input) {
+                return VariantRun {
+                    part: variant.part,
+                    name: variant.name,
+                    answer: Err(reason.clone().into()),
+                    time: Duration::ZERO,
+                    skipped: Some(reason),
+                };
+            }
+
+            let mut times = vec![];
+            let mut answer = Err(BoxError::from("never ran"));
+
+            for _ in 0..runs.max(1) {
+                let start = Instant::now();
+                answer = (variant.solve)(input).map_err(Into::into);
+                times.push(start.elapsed());
+
+                if answer.is_err() {
+                    break;
+                }
+            }
+
+            VariantRun {
+                part: variant.part,
+                name: variant.name,
+                answer,
+                time: median(times),
+                skipped: None,
+            }
+        })
+        .collect()
+}
+
+/// Parts whose variants that ran don't all give the same answer. Errors
+/// count as the same when their messages are
+pub fn disagreements(variant_runs: &[VariantRun]) -> Vec<u8> {
+    let mut parts: Vec<u8> = variant_runs.iter().map(|run| run.part).collect();
+    parts.dedup();
+
+    parts
+        .into_iter()
+        .filter(|&part| {
+            let mut outcomes = variant_runs
+                .iter()
+                .filter(|run| run.part == part && run.skipped.is_none())
+                .map(|run| run.answer.as_ref().map_err(ToString::to_string));
+            let first = outcomes.next();
+            outcomes.any(|outcome| Some(outcome) != first)
+        })
+        .collect()
+}
+
+fn median(mut times: Vec<Duration>) -> Duration {
+    times.sort();
+    times[times.len() / 2]
+}
+
+/// Every recorded run, oldest first. A missing history file has no runs
+pub fn load_history(path: &Path) -> Result<Vec<Record>, BoxError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(format!("failed to read {}: {err}", path.display()).into()),
+    };
+
+    serde_json::from_str(&text).map_err(|err| format!("{}: {err}", path.display()).into())
+}
+
+/// Append `record` to the history file
+pub fn record(path: &Path, record: Record) -> Result<usize, BoxError> {
+    let mut history = load_history(path)?;
+    history.push(record);
+
+    let json = serde_json::to_string_pretty(&history)?;
+    fs::write(path, json + "\n")
+        .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+
+    Ok(history.len())
+}
+
+/// Record of timings taken now
+pub fn new_record(timings: Vec<Timing>) -> Record {
+    Record {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs()),
+        parallel: cfg!(feature = "parallel"),
+        timings,
+    }
+}
+
+/// Compare every timing of the `latest` run with the same day, input and
+/// phase in the `previous` run
+pub fn compare(previous: &Record, latest: &Record) -> Vec<Change> {
+    latest
+        .timings
+        .iter()
+        .map(|timing| Change {
+            day: timing.day,
+            input: timing.input.clone(),
+            phase: timing.phase.clone(),
+            previous: previous
+                .timings
+                .iter()
+                .find(|old| {
+                    old.day == timing.day && old.input == timing.input && old.phase == timing.phase
+                })
+                .map(|old| Duration::from_nanos(old.nanos)),
+            latest: Duration::from_nanos(timing.nanos),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: u8, phase: &str, nanos: u64) -> Timing {
+        Timing {
+            day,
+            input: "input".to_string(),
+            phase: phase.to_string(),
+            nanos,
+        }
+    }
+
+    #[test]
+    fn compare_test() {
+        let previous = new_record(vec![timing(1, "parse", 1000), timing(1, "part1", 1000)]);
+        let latest = new_record(vec![
+            timing(1, "parse", 1050),
+            timing(1, "part1", 1200),
+            timing(1, "part2", 500),
+        ]);
+        let changes = compare(&previous, &latest);
+
+        assert_eq!(changes.len(), 3);
+        assert!(!changes[0].is_regression(10.0));
+        assert!(changes[1].is_regression(10.0));
+        assert!(!changes[1].is_regression(25.0));
+        assert_eq!(changes[2].previous, None);
+        assert!(!changes[2].is_regression(10.0));
+    }
+
+    #[test]
+    fn history_test() {
+        let dir = std::env::temp_dir().join(format!("aoc-bench-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("history.json");
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(load_history(&path).unwrap(), vec![]);
+
+        let run = new_record(vec![timing(7, "part2", 42)]);
+        assert_eq!(record(&path, run.clone()).unwrap(), 1);
+        assert_eq!(record(&path, run.clone()).unwrap(), 2);
+        assert_eq!(load_history(&path).unwrap(), vec![run.clone(), run]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn measure_variants_test() {
+        let half = |input: &str| -> Result<Answer, String> {
+            let n: u64 = input
+                .parse()
+                .map_err(|_| format!("`{input}` isn't a number"))?;
+            Ok((n / 2).into())
+        };
+        let shifted = |input: &str| -> Result<Answer, String> {
+            let n: u64 = input
+                .parse()
+                .map_err(|_| format!("`{input}` isn't a number"))?;
+            Ok((n >> 1).into())
+        };
+        let rounded = |input: &str| -> Result<Answer, String> {
+            let n: u64 = input
+                .parse()
+                .map_err(|_| format!("`{input}` isn't a number"))?;
+            Ok(n.div_ceil(2).into())
+        };
+        let variants = [
+            Variant::new("half", 1, half),
+            Variant::new("shifted", 1, shifted),
+            Variant::new("rounded", 1, rounded)
+                .unless(|input| (input == "7").then(|| "rounds 7 up".to_string())),
+            Variant::new("half", 2, half),
+            Variant::new("rounded", 2, rounded),
+        ];
+
+        let variant_runs = measure_variants(&variants, "7", 3);
+        let names: Vec<(u8, &str)> = variant_runs
+            .iter()
+            .map(|run| (run.part, run.name))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                (1, "half"),
+                (1, "shifted"),
+                (1, "rounded"),
+                (2, "half"),
+                (2, "rounded")
+            ]
+        );
+        assert_eq!(variant_runs[2].skipped.as_deref(), Some("rounds 7 up"));
+        assert_eq!(disagreements(&variant_runs), [2]);
+
+        assert!(disagreements(&measure_variants(&variants, "8", 3)).is_empty());
+        assert!(disagreements(&measure_variants(&variants, "x", 3)).is_empty());
+    }
+
+    #[test]
+    fn measure_test() {
+        let measured = measure(8, "LR\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)", 3).unwrap();
+        let phases: Vec<&str> = measured.timings.iter().map(|(phase, _)| *phase).collect();
+
+        // day 8 has no part 2 yet
+        assert_eq!(phases, ["parse", "part1"]);
+        assert!(measured.error.is_none());
+    }
+}
@@ -0,0 +1,436 @@
+use std::{io, time::Duration};
+
+use common::Answer;
+use ratatui::{
+    backend::Backend,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Paragraph, Row, Table, TableState},
+    Frame, Terminal,
+};
+
+use crate::{
+    days::{self, BoxError},
+    explain::Trace,
+};
+
+/// Which input each day is solved against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// The day's first example
+    Example,
+    /// The real puzzle input
+    Real,
+}
+
+/// Where a part stands after its last run
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Solved(Answer),
+    Failing(String),
+    NotImplemented,
+}
+
+/// Status of one part of a day, and how long its last run took
+pub struct PartRow {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    /// `None` when the part didn't run, as when the input couldn't be read
+    /// or the part isn't implemented
+    pub time: Option<Duration>,
+}
+
+/// Trace of a part, opened over the table of days
+struct TraceView {
+    day: u8,
+    part: u8,
+    trace: Result<Trace, String>,
+    selected: usize,
+}
+
+/// Every day's parts with their status, the last answer and timing of
+/// each, re-run and explained from the keyboard. `load` reads a day's
+/// input, returning its name along with it
+pub struct Dashboard<L> {
+    load: L,
+    kind: InputKind,
+    /// Name and text of the input each day last ran against
+    inputs: Vec<(String, Result<String, String>)>,
+    rows: Vec<PartRow>,
+    selected: usize,
+    trace: Option<TraceView>,
+    quit: bool,
+}
+
+impl<L: Fn(u8, InputKind) -> (String, Result<String, BoxError>)> Dashboard<L> {
+    /// Dashboard with every day run against its example
+    pub fn new(load: L) -> Self {
+        let mut dashboard = Dashboard {
+            load,
+            kind: InputKind::Example,
+            inputs: vec![],
+            rows: vec![],
+            selected: 0,
+            trace: None,
+            quit: false,
+        };
+        dashboard.run_all();
+        dashboard
+    }
+
+    /// Whether a key asked to leave the dashboard
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    fn run_all(&mut self) {
+        self.inputs.clear();
+        self.rows.clear();
+
+        for day in days::DAYS {
+            let (name, input) = (self.load)(day, self.kind);
+            let input = input.map_err(|err| err.to_string());
+            self.rows.extend(part_rows(day, &input));
+            self.inputs.push((name, input));
+        }
+    }
+
+    /// Run the day of the selected part again, reading its input afresh
+    fn run_selected(&mut self) {
+        let day = self.rows[self.selected].day;
+        let (name, input) = (self.load)(day, self.kind);
+        let input = input.map_err(|err| err.to_string());
+
+        let first = self.rows.iter().position(|row| row.day == day).unwrap();
+        let count = self.rows.iter().filter(|row| row.day == day).count();
+        self.rows
+            .splice(first..first + count, part_rows(day, &input));
+        self.inputs[usize::from(day) - 1] = (name, input);
+    }
+
+    fn open_trace(&mut self) {
+        let PartRow { day, part, .. } = self.rows[self.selected];
+        let trace = match &self.inputs[usize::from(day) - 1].1 {
+            Ok(input) => match days::explain(day, input, part) {
+                Some(trace) => trace.map_err(|err| err.to_string()),
+                None => Err(format!("day {day} has no solution")),
+            },
+            Err(err) => Err(err.clone()),
+        };
+
+        self.trace = Some(TraceView {
+            day,
+            part,
+            trace,
+            selected: 0,
+        });
+    }
+
+    /// Act on a key: arrows or `j` and `k` move, `r` re-runs the selected
+    /// day, `a` re-runs every day, `t` toggles between the examples and the
+    /// real inputs, enter opens the selected part's trace and `q` closes
+    /// the trace or quits
+    pub fn handle(&mut self, key: KeyCode) {
+        if let Some(trace) = &mut self.trace {
+            let steps = trace.trace.as_ref().map_or(0, |trace| trace.steps.len());
+            match key {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    trace.selected = trace.selected.saturating_sub(1)
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    trace.selected = (trace.selected + 1).min(steps.saturating_sub(1));
+                }
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => self.trace = None,
+                _ => {}
+            }
+            return;
+        }
+
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.rows.len() - 1);
+            }
+            KeyCode::Char('r') => self.run_selected(),
+            KeyCode::Char('a') => self.run_all(),
+            KeyCode::Char('t') => {
+                self.kind = match self.kind {
+                    InputKind::Example => InputKind::Real,
+                    InputKind::Real => InputKind::Example,
+                };
+                self.run_all();
+            }
+            KeyCode::Enter => self.open_trace(),
+            KeyCode::Esc | KeyCode::Char('q') => self.quit = true,
+            _ => {}
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let [body, help] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+
+        match &self.trace {
+            Some(trace) => {
+                self.draw_trace(frame, trace, body);
+                frame.render_widget(Paragraph::new("↑↓ scroll  enter/q back"), help);
+            }
+            None => {
+                self.draw_days(frame, body);
+                frame.render_widget(
+                    Paragraph::new(
+                        "↑↓ select  r re-run day  a re-run all  t toggle input  enter explain  q quit",
+                    ),
+                    help,
+                );
+            }
+        }
+    }
+
+    fn draw_days(&self, frame: &mut Frame, area: Rect) {
+        let title = match self.kind {
+            InputKind::Example => " Advent of Code 2023: examples ",
+            InputKind::Real => " Advent of Code 2023: real inputs ",
+        };
+
+        let rows = self.rows.iter().map(|row| {
+            let (status, answer, color) = match &row.status {
+                Status::Solved(answer) => ("solved", answer.to_string(), Color::Green),
+                Status::Failing(err) => ("failing", err.clone(), Color::Red),
+                Status::NotImplemented => ("not implemented", String::new(), Color::DarkGray),
+            };
+            let time = row.time.map_or(String::new(), |time| format!("{time:.2?}"));
+            let input = &self.inputs[usize::from(row.day) - 1].0;
+
+            Row::new([
+                row.day.to_string(),
+                row.part.to_string(),
+                input.clone(),
+                status.to_string(),
+                answer,
+                time,
+            ])
+            .style(Style::new().fg(color))
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(3),
+                Constraint::Length(4),
+                Constraint::Length(24),
+                Constraint::Length(15),
+                Constraint::Fill(1),
+                Constraint::Length(10),
+            ],
+        )
+        .header(
+            Row::new(["Day", "Part", "Input", "Status", "Answer", "Time"])
+                .style(Style::new().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::bordered().title(title))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    fn draw_trace(&self, frame: &mut Frame, trace: &TraceView, area: Rect) {
+        let title = format!(" Day {} part {} ", trace.day, trace.part);
+
+        let explained = match &trace.trace {
+            Ok(explained) => explained,
+            Err(err) => {
+                frame.render_widget(
+                    Paragraph::new(err.as_str())
+                        .style(Style::new().fg(Color::Red))
+                        .block(Block::bordered().title(title)),
+                    area,
+                );
+                return;
+            }
+        };
+
+        let mut table = explained.table().into_iter();
+        let header = table.next().unwrap_or_default();
+        let rows: Vec<Vec<String>> = table.collect();
+        let widths: Vec<Constraint> = (0..header.len())
+            .map(|column| {
+                let width = rows
+                    .iter()
+                    .chain([&header])
+                    .map(|row| row[column].chars().count())
+                    .max()
+                    .unwrap_or(0);
+                Constraint::Length(u16::try_from(width).unwrap_or(u16::MAX))
+            })
+            .collect();
+
+        let table = Table::new(rows.into_iter().map(Row::new), widths)
+            .header(Row::new(header).style(Style::new().add_modifier(Modifier::BOLD)))
+            .block(Block::bordered().title(format!("{title}answer {} ", explained.answer)))
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+        let mut state = TableState::default().with_selected(Some(trace.selected));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}
+
+/// Run `day` against its `input` and give the status of each part
+fn part_rows(day: u8, input: &Result<String, String>) -> Vec<PartRow> {
+    let failing = |err: &dyn std::fmt::Display| {
+        [1, 2]
+            .map(|part| PartRow {
+                day,
+                part,
+                status: Status::Failing(err.to_string()),
+                time: None,
+            })
+            .into()
+    };
+
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => return failing(err),
+    };
+    let day_run = match days::run(day, input, &[1, 2]) {
+        Some(Ok(day_run)) => day_run,
+        Some(Err(err)) => return failing(&err),
+        None => return failing(&format!("day {day} has no solution")),
+    };
+
+    day_run
+        .parts
+        .into_iter()
+        .map(|part_run| {
+            let (status, time) = match part_run.answer {
+                Ok(Some(answer)) => (Status::Solved(answer), Some(part_run.time)),
+                Ok(None) => (Status::NotImplemented, None),
+                Err(err) => (Status::Failing(err.to_string()), Some(part_run.time)),
+            };
+            PartRow {
+                day,
+                part: part_run.part,
+                status,
+                time,
+            }
+        })
+        .collect()
+}
+
+/// Draw the dashboard and act on keys until one asks to quit
+pub fn run<B: Backend, L>(
+    terminal: &mut Terminal<B>,
+    dashboard: &mut Dashboard<L>,
+) -> io::Result<()>
+where
+    L: Fn(u8, InputKind) -> (String, Result<String, BoxError>),
+    io::Error: From<B::Error>,
+{
+    while !dashboard.should_quit() {
+        terminal.draw(|frame| dashboard.draw(frame))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                dashboard.handle(key.code);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+
+    /// Each day's first example, or a small generated input for real ones
+    /// but day 3's, which can't be read
+    fn load(day: u8, kind: InputKind) -> (String, Result<String, BoxError>) {
+        match kind {
+            InputKind::Example => {
+                let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+                let examples = examples::load(&crate::answers::examples_dir(&root, day)).unwrap();
+                (examples[0].name.clone(), Ok(examples[0].input().unwrap()))
+            }
+            InputKind::Real if day == 3 => ("missing".to_string(), Err("no such file".into())),
+            InputKind::Real => (
+                "generated".to_string(),
+                Ok(days::generate(day, 0, 5).unwrap()),
+            ),
+        }
+    }
+
+    fn screen<L>(dashboard: &Dashboard<L>) -> String
+    where
+        L: Fn(u8, InputKind) -> (String, Result<String, BoxError>),
+    {
+        let mut terminal = Terminal::new(TestBackend::new(120, 24)).unwrap();
+        terminal.draw(|frame| dashboard.draw(frame)).unwrap();
+        terminal.backend().to_string()
+    }
+
+    fn status<L>(dashboard: &Dashboard<L>, day: u8, part: u8) -> &Status {
+        &dashboard
+            .rows
+            .iter()
+            .find(|row| row.day == day && row.part == part)
+            .unwrap()
+            .status
+    }
+
+    #[test]
+    fn status_test() {
+        let dashboard = Dashboard::new(load);
+
+        assert_eq!(dashboard.rows.len(), 16);
+        assert_eq!(status(&dashboard, 1, 1), &Status::Solved(Answer::from(142)));
+        assert_eq!(status(&dashboard, 8, 2), &Status::NotImplemented);
+
+        let screen = screen(&dashboard);
+        assert!(screen.contains("Advent of Code 2023: examples"));
+        assert!(screen.contains("not implemented"));
+        assert!(screen.contains("142"));
+    }
+
+    #[test]
+    fn keys_test() {
+        let mut dashboard = Dashboard::new(load);
+
+        dashboard.handle(KeyCode::Char('t'));
+        assert_eq!(dashboard.kind, InputKind::Real);
+        assert_eq!(
+            status(&dashboard, 3, 1),
+            &Status::Failing("no such file".to_string())
+        );
+        assert!(screen(&dashboard).contains("real inputs"));
+
+        // down to day 2 part 1 and run it again
+        for _ in 0..2 {
+            dashboard.handle(KeyCode::Down);
+        }
+        dashboard.handle(KeyCode::Char('r'));
+        assert!(matches!(status(&dashboard, 2, 1), Status::Solved(_)));
+
+        dashboard.handle(KeyCode::Char('t'));
+        assert_eq!(dashboard.kind, InputKind::Example);
+
+        dashboard.handle(KeyCode::Enter);
+        let trace = screen(&dashboard);
+        assert!(trace.contains("Day 2 part 1 answer 8"));
+        assert!(trace.contains("possible"));
+
+        dashboard.handle(KeyCode::Char('q'));
+        assert!(!dashboard.should_quit());
+        assert!(screen(&dashboard).contains("not implemented"));
+
+        dashboard.handle(KeyCode::Char('q'));
+        assert!(dashboard.should_quit());
+    }
+}
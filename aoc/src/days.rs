@@ -0,0 +1,548 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    fs::File,
+    io::BufReader,
+    ops::RangeInclusive,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use common::{Answer, Counterexample, Explanation, Position, Rng, Solution};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    bench::{self, VariantRun},
+    explain::Trace,
+    lint::Diagnostic,
+};
+
+/// Error from parsing or solving any day
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// Days that have a solution crate in the workspace
+pub const DAYS: RangeInclusive<u8> = 1..=8;
+
+/// Days that can be solved from a reader one line at a time
+pub const STREAMED_DAYS: [u8; 4] = [1, 2, 4, 7];
+
+/// Days with a naive reference solver to check the solution against
+pub const NAIVE_DAYS: [u8; 5] = [1, 4, 5, 6, 7];
+
+/// Answers and timings from running a single day
+pub struct DayRun {
+    pub parse_time: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+pub struct PartRun {
+    pub part: u8,
+    /// `Ok(None)` when the part has no solution yet
+    pub answer: Result<Option<Answer>, BoxError>,
+    pub time: Duration,
+}
+
+/// Parse a day's input and solve the requested parts, returning `None`
+/// when the day has no solution
+pub fn run(day: u8, input: &str, parts: &[u8]) -> Option<Result<DayRun, BoxError>> {
+    let day_run = match day {
+        1 => run_solution::<day1::Day1>(input, parts),
+        2 => run_solution::<day2::Day2>(input, parts),
+        3 => run_solution::<day3::Day3>(input, parts),
+        4 => run_solution::<day4::Day4>(input, parts),
+        5 => run_solution::<day5::Day5>(input, parts),
+        6 => run_solution::<day6::Day6>(input, parts),
+        7 => run_solution::<day7::Day7>(input, parts),
+        8 => run_solution::<day8::Day8>(input, parts),
+        _ => return None,
+    };
+
+    Some(day_run)
+}
+
+/// Solve the requested parts of a day from its parsed model as JSON, as
+/// printed by `dump`, instead of its puzzle input. `None` when the day
+/// has no solution
+pub fn run_json(day: u8, json: &str, parts: &[u8]) -> Option<Result<DayRun, BoxError>> {
+    let day_run = match day {
+        1 => run_model::<day1::Day1>(json, parts),
+        2 => run_model::<day2::Day2>(json, parts),
+        3 => run_model::<day3::Day3>(json, parts),
+        4 => run_model::<day4::Day4>(json, parts),
+        5 => run_model::<day5::Day5>(json, parts),
+        6 => run_model::<day6::Day6>(json, parts),
+        7 => run_model::<day7::Day7>(json, parts),
+        8 => run_model::<day8::Day8>(json, parts),
+        _ => return None,
+    };
+
+    Some(day_run)
+}
+
+/// Parse a day's input and write its model as pretty JSON. `None` when
+/// the day has no solution
+pub fn dump(day: u8, input: &str) -> Option<Result<String, BoxError>> {
+    let json = match day {
+        1 => dump_model::<day1::Day1>(input),
+        2 => dump_model::<day2::Day2>(input),
+        3 => dump_model::<day3::Day3>(input),
+        4 => dump_model::<day4::Day4>(input),
+        5 => dump_model::<day5::Day5>(input),
+        6 => dump_model::<day6::Day6>(input),
+        7 => dump_model::<day7::Day7>(input),
+        8 => dump_model::<day8::Day8>(input),
+        _ => return None,
+    };
+
+    Some(json)
+}
+
+/// Answer to a single part along with the steps that led to it, failing
+/// when the part has no solution. `None` when the day has no solution
+pub fn explain(day: u8, input: &str, part: u8) -> Option<Result<Trace, BoxError>> {
+    let explained = match (day, part) {
+        (1, 1) => trace(day1::explain_part1(input)),
+        (1, _) => trace(day1::explain_part2(input)),
+        (2, 1) => trace(day2::explain_part1(input)),
+        (2, _) => trace(day2::explain_part2(input)),
+        (3, 1) => trace(day3::explain_part1(input)),
+        (3, _) => trace(day3::explain_part2(input)),
+        (4, 1) => trace(day4::explain_part1(input)),
+        (4, _) => trace(day4::explain_part2(input)),
+        (5, 1) => trace(day5::explain_part1(input)),
+        (5, _) => trace(day5::explain_part2(input)),
+        (6, 1) => trace(day6::explain_part1(input)),
+        (6, _) => trace(day6::explain_part2(input)),
+        (7, 1) => trace(day7::explain_part1(input)),
+        (7, _) => trace(day7::explain_part2(input)),
+        (8, 1) => trace(day8::explain_part1(input)),
+        (8, _) => Err(format!("day {day} part {part} is not implemented").into()),
+        _ => return None,
+    };
+
+    Some(explained)
+}
+
+/// Every problem in a day's input, in order, rather than just the first
+/// that parsing stops at. `None` when the day has no solution
+pub fn lint(day: u8, input: &str) -> Option<Vec<Diagnostic>> {
+    let mut diagnostics = match day {
+        1 => diagnostics(day1::lint(input), |err| Some(err.position())),
+        2 => diagnostics(day2::lint(input), |err| Some(err.position())),
+        3 => diagnostics(day3::lint(input), |err| Some(err.position())),
+        4 => diagnostics(day4::lint(input), |err| Some(err.position())),
+        5 => diagnostics(day5::lint(input), |err| Some(err.position())),
+        6 => diagnostics(day6::lint(input), day6::ParseError::position),
+        7 => diagnostics(day7::lint(input), |err| Some(err.position())),
+        8 => diagnostics(day8::lint(input), day8::ParseError::position),
+        _ => return None,
+    };
+    crate::lint::sort(&mut diagnostics);
+
+    Some(diagnostics)
+}
+
+/// Answer to a single part, failing when the day or part has no solution
+pub fn solve(day: u8, input: &str, part: u8) -> Result<Answer, BoxError> {
+    let day_run = run(day, input, &[part]).ok_or(format!("day {day} has no solution"))??;
+
+    match day_run
+        .parts
+        .into_iter()
+        .next()
+        .map(|part_run| part_run.answer)
+    {
+        Some(Ok(Some(answer))) => Ok(answer),
+        Some(Err(err)) => Err(err),
+        _ => Err(format!("day {day} part {part} is not implemented").into()),
+    }
+}
+
+/// Answer to a single part, streaming the input file instead of reading
+/// it into memory. `None` when the day can't be streamed
+pub fn stream(day: u8, path: &Path, part: u8) -> Option<Result<Answer, BoxError>> {
+    if !STREAMED_DAYS.contains(&day) {
+        return None;
+    }
+
+    let reader = match File::open(path) {
+        Ok(file) => BufReader::new(file),
+        Err(err) => return Some(Err(err.into())),
+    };
+
+    let answer = match (day, part) {
+        (1, 1) => day1::part1_reader(reader).map_err(BoxError::from),
+        (1, _) => day1::part2_reader(reader).map_err(BoxError::from),
+        (2, 1) => day2::part1_reader(reader).map_err(BoxError::from),
+        (2, _) => day2::part2_reader(reader).map_err(BoxError::from),
+        (4, 1) => day4::part1_reader(reader).map_err(BoxError::from),
+        (4, _) => day4::part2_reader(reader).map_err(BoxError::from),
+        (7, 1) => day7::part1_reader(reader).map_err(BoxError::from),
+        _ => day7::part2_reader(reader).map_err(BoxError::from),
+    };
+
+    Some(answer)
+}
+
+/// Random input for a day, the same for the same seed and size. `None`
+/// when the day has no solution
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<String> {
+    let rng = &mut Rng::new(seed);
+
+    let input = match day {
+        1 => day1::generate(rng, size),
+        2 => day2::generate(rng, size),
+        3 => day3::generate(rng, size),
+        4 => day4::generate(rng, size),
+        5 => day5::generate(rng, size),
+        6 => day6::generate(rng, size),
+        7 => day7::generate(rng, size),
+        8 => day8::generate(rng, size),
+        _ => return None,
+    };
+
+    Some(input)
+}
+
+/// Compare a part's solution to the day's naive reference solver on
+/// `cases` small generated inputs, starting from `seed`, with the first
+/// disagreement cut down to a counterexample. `None` when the day has no
+/// naive solver
+pub fn differential(
+    day: u8,
+    part: u8,
+    seed: u64,
+    cases: u64,
+) -> Option<Result<(), Counterexample>> {
+    use common::differential as check;
+
+    let checked = match (day, part) {
+        (1, 1) => check(
+            seed,
+            cases,
+            day1::generate_small,
+            day1::naive_part1,
+            day1::try_part1,
+        ),
+        (1, _) => check(
+            seed,
+            cases,
+            day1::generate_small,
+            day1::naive_part2,
+            day1::try_part2,
+        ),
+        (4, 1) => check(
+            seed,
+            cases,
+            day4::generate_small,
+            day4::naive_part1,
+            day4::try_part1,
+        ),
+        (4, _) => check(
+            seed,
+            cases,
+            day4::generate_small,
+            day4::naive_part2,
+            day4::try_part2,
+        ),
+        (5, 1) => check(
+            seed,
+            cases,
+            day5::generate_small,
+            day5::naive_part1,
+            day5::try_part1,
+        ),
+        (5, _) => check(
+            seed,
+            cases,
+            day5::generate_small,
+            day5::naive_part2,
+            day5::try_part2,
+        ),
+        (6, 1) => check(
+            seed,
+            cases,
+            day6::generate_small,
+            day6::naive_part1,
+            day6::try_part1,
+        ),
+        (6, _) => check(
+            seed,
+            cases,
+            day6::generate_small,
+            day6::naive_part2,
+            day6::try_part2,
+        ),
+        (7, 1) => check(
+            seed,
+            cases,
+            day7::generate_small,
+            day7::naive_part1,
+            day7::try_part1,
+        ),
+        (7, _) => check(
+            seed,
+            cases,
+            day7::generate_small,
+            day7::naive_part2,
+            day7::try_part2,
+        ),
+        _ => return None,
+    };
+
+    Some(checked)
+}
+
+/// Answer and median time of every variant of a day's parts against
+/// `input`. `None` when the day has no variants
+pub fn compare(day: u8, input: &str, runs: usize) -> Option<Vec<VariantRun>> {
+    let variant_runs = match day {
+        5 => bench::measure_variants(day5::VARIANTS, input, runs),
+        6 => bench::measure_variants(day6::VARIANTS, input, runs),
+        7 => bench::measure_variants(day7::VARIANTS, input, runs),
+        8 => bench::measure_variants(day8::VARIANTS, input, runs),
+        _ => return None,
+    };
+
+    Some(variant_runs)
+}
+
+fn trace<T: Serialize, E: Into<BoxError>>(
+    explanation: Result<Explanation<T>, E>,
+) -> Result<Trace, BoxError> {
+    Trace::new(explanation.map_err(Into::into)?)
+}
+
+/// Diagnostics for the errors a lint found, leaving out any without a
+/// `position`. Those come from solving an input, which linting doesn't do
+fn diagnostics<E: Display>(
+    errors: Vec<E>,
+    position: fn(&E) -> Option<Position>,
+) -> Vec<Diagnostic> {
+    errors
+        .iter()
+        .filter_map(|err| Some(Diagnostic::new(position(err)?, err)))
+        .collect()
+}
+
+fn run_solution<S: Solution>(input: &str, parts: &[u8]) -> Result<DayRun, BoxError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    Ok(solve_parts::<S>(&parsed, start.elapsed(), parts))
+}
+
+fn run_model<S: Solution>(json: &str, parts: &[u8]) -> Result<DayRun, BoxError>
+where
+    S::Input: DeserializeOwned,
+{
+    let start = Instant::now();
+    let parsed: S::Input = serde_json::from_str(json)?;
+    Ok(solve_parts::<S>(&parsed, start.elapsed(), parts))
+}
+
+fn dump_model<S: Solution>(input: &str) -> Result<String, BoxError>
+where
+    S::Input: Serialize,
+{
+    Ok(serde_json::to_string_pretty(&S::parse(input)?)?)
+}
+
+fn solve_parts<S: Solution>(parsed: &S::Input, parse_time: Duration, parts: &[u8]) -> DayRun {
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part1(parsed).map(Some),
+                _ => S::part2(parsed),
+            };
+
+            PartRun {
+                part,
+                answer: answer.map_err(BoxError::from),
+                time: start.elapsed(),
+            }
+        })
+        .collect();
+
+    DayRun { parse_time, parts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_matches_run_test() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+
+        for day in STREAMED_DAYS {
+            for example in examples::load(&crate::answers::examples_dir(&root, day)).unwrap() {
+                let input = example.input().unwrap();
+
+                for part in [1, 2]
+                    .into_iter()
+                    .filter(|&part| example.answer(part).is_some())
+                {
+                    assert_eq!(
+                        stream(day, &example.path, part).unwrap().unwrap(),
+                        solve(day, &input, part).unwrap(),
+                        "day {day} part {part} against {}",
+                        example.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn json_round_trip_test() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+
+        for day in DAYS {
+            for example in examples::load(&crate::answers::examples_dir(&root, day)).unwrap() {
+                let input = example.input().unwrap();
+                let json = dump(day, &input).unwrap().unwrap();
+
+                let answers = |day_run: DayRun| -> Vec<String> {
+                    day_run
+                        .parts
+                        .into_iter()
+                        .map(|part_run| format!("{:?}", part_run.answer.ok()))
+                        .collect()
+                };
+                assert_eq!(
+                    answers(run_json(day, &json, &[1, 2]).unwrap().unwrap()),
+                    answers(run(day, &input, &[1, 2]).unwrap().unwrap()),
+                    "day {day} against {}",
+                    example.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn explain_matches_run_test() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+
+        for day in DAYS {
+            for example in examples::load(&crate::answers::examples_dir(&root, day)).unwrap() {
+                let input = example.input().unwrap();
+
+                for part in [1, 2]
+                    .into_iter()
+                    .filter(|&part| example.answer(part).is_some())
+                {
+                    let trace = explain(day, &input, part).unwrap().unwrap();
+                    assert_eq!(
+                        trace.answer,
+                        solve(day, &input, part).unwrap(),
+                        "day {day} part {part} against {}",
+                        example.name
+                    );
+                    assert!(!trace.steps.is_empty());
+                }
+            }
+        }
+
+        assert!(explain(8, "LR", 2).unwrap().is_err());
+        assert!(explain(9, "", 1).is_none());
+    }
+
+    #[test]
+    fn differential_test() {
+        for day in NAIVE_DAYS {
+            for part in [1, 2] {
+                if let Err(counterexample) = differential(day, part, 0, 2000).unwrap() {
+                    panic!("day {day} part {part}: {counterexample}");
+                }
+            }
+        }
+
+        assert!(differential(8, 1, 0, 100).is_none());
+    }
+
+    #[test]
+    fn compare_test() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+
+        for day in DAYS {
+            for example in examples::load(&crate::answers::examples_dir(&root, day)).unwrap() {
+                let Some(variant_runs) = compare(day, &example.input().unwrap(), 1) else {
+                    continue;
+                };
+
+                assert!(
+                    bench::disagreements(&variant_runs).is_empty(),
+                    "day {day} against {}",
+                    example.name
+                );
+                for run in &variant_runs {
+                    if let Some(expected) = example.answer(run.part) {
+                        assert_eq!(
+                            run.answer.as_ref().map(ToString::to_string).ok().as_deref(),
+                            Some(expected),
+                            "day {day} part {} {} against {}",
+                            run.part,
+                            run.name,
+                            example.name
+                        );
+                    }
+                }
+            }
+        }
+
+        assert!(compare(1, "1", 1).is_none());
+    }
+
+    #[test]
+    fn lint_test() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+
+        for day in DAYS {
+            // an example for part 2 alone, like day 1's spelled out digits,
+            // needn't be valid for part 1
+            for example in examples::load(&crate::answers::examples_dir(&root, day))
+                .unwrap()
+                .into_iter()
+                .filter(|example| example.answer(1).is_some())
+            {
+                assert_eq!(
+                    lint(day, &example.input().unwrap()),
+                    Some(vec![]),
+                    "day {day} against {}",
+                    example.name
+                );
+            }
+        }
+
+        let diagnostics = lint(8, "LXR\n\nAAA = (BBB, CCC)\nCCC = CCC, CCC)\n").unwrap();
+        let positions: Vec<String> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.position.to_string())
+            .collect();
+        assert_eq!(positions, ["1:2", "3:8", "3:13", "4:16"]);
+        assert_eq!(lint(9, ""), None);
+    }
+
+    #[test]
+    fn invalid_json_test() {
+        let bad_hand = r#"[{"hand": "32T3X", "bid": 765}]"#;
+        let bad_seed_ranges = r#"{
+            "seeds": [79, 14],
+            "seed_ranges": [{"start": 79, "end": 90}],
+            "mappings": []
+        }"#;
+        let undefined_node = r#"{"instructions": "L", "network": {"AAA": ["ZZZ", "AAA"]}}"#;
+
+        assert!(run_json(7, bad_hand, &[1])
+            .unwrap()
+            .is_err_and(|err| err.to_string().contains("found `X`")));
+        assert!(run_json(5, bad_seed_ranges, &[1])
+            .unwrap()
+            .is_err_and(|err| err.to_string().contains("seed ranges don't match")));
+        assert!(run_json(8, undefined_node, &[1])
+            .unwrap()
+            .is_err_and(|err| err.to_string().contains("node `ZZZ` is never defined")));
+    }
+}
@@ -0,0 +1,120 @@
+use common::{Answer, Explanation};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::days::BoxError;
+
+/// Answer to a part and the steps that led to it, each step a JSON object
+/// with the same fields in the same order
+pub struct Trace {
+    pub answer: Answer,
+    pub steps: Vec<Value>,
+}
+
+impl Trace {
+    pub fn new<T: Serialize>(explanation: Explanation<T>) -> Result<Self, BoxError> {
+        let steps = explanation
+            .steps
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Trace {
+            answer: explanation.answer,
+            steps,
+        })
+    }
+
+    /// Header of the step fields followed by a row for each step
+    pub fn table(&self) -> Vec<Vec<String>> {
+        let header: Vec<String> = match self.steps.first() {
+            Some(Value::Object(fields)) => fields.keys().cloned().collect(),
+            _ => return vec![],
+        };
+
+        let rows = self.steps.iter().map(|step| {
+            header
+                .iter()
+                .map(|field| step.get(field).map_or(String::new(), cell))
+                .collect()
+        });
+
+        [header.clone()].into_iter().chain(rows).collect()
+    }
+
+    /// The answer and every step as pretty JSON
+    pub fn to_json(&self) -> String {
+        let trace = serde_json::json!({
+            "answer": self.answer.to_string(),
+            "steps": self.steps,
+        });
+        // a JSON value can always be written out
+        serde_json::to_string_pretty(&trace).unwrap()
+    }
+}
+
+/// Text of a table cell, lists as their items separated by spaces
+fn cell(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(cell).collect::<Vec<_>>().join(" "),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Step {
+        card: u32,
+        matching: Vec<u32>,
+        hand: &'static str,
+    }
+
+    #[test]
+    fn table_test() {
+        let explanation = Explanation::new(
+            8u32,
+            vec![
+                Step {
+                    card: 2,
+                    matching: vec![83, 86],
+                    hand: "32T3K",
+                },
+                Step {
+                    card: 1,
+                    matching: vec![],
+                    hand: "KK677",
+                },
+            ],
+        );
+        let trace = Trace::new(explanation).unwrap();
+
+        assert_eq!(
+            trace.table(),
+            [
+                ["card", "matching", "hand"],
+                ["2", "83 86", "32T3K"],
+                ["1", "", "KK677"],
+            ]
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&trace.to_json()).unwrap(),
+            serde_json::json!({
+                "answer": "8",
+                "steps": [
+                    {"card": 2, "matching": [83, 86], "hand": "32T3K"},
+                    {"card": 1, "matching": [], "hand": "KK677"},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn empty_table_test() {
+        let trace = Trace::new(Explanation::<Step>::new(0u32, vec![])).unwrap();
+        assert!(trace.table().is_empty());
+    }
+}
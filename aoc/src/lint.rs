@@ -0,0 +1,113 @@
+use std::fmt::Display;
+
+use common::Position;
+
+/// Problem found in a puzzle input, with the message of the parse error
+/// it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub position: Position,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Diagnostic for a day's parse error, which displays as
+    /// `line:column: message`
+    pub fn new(position: Position, error: &impl Display) -> Self {
+        let error = error.to_string();
+        let message = error
+            .strip_prefix(&format!("{position}: "))
+            .unwrap_or(&error)
+            .to_string();
+
+        Diagnostic { position, message }
+    }
+}
+
+/// Sort diagnostics by where they are in the input
+pub fn sort(diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.position.line, diagnostic.position.column));
+}
+
+/// Render a diagnostic as `path:line:column: message` followed by the
+/// offending line with a caret under the column:
+///
+/// ```text
+/// day2/input.txt:2:19: expected red, green or blue, found `purple`
+///   |
+/// 2 | Game 2: 1 blue, 2 purple
+///   |                   ^
+/// ```
+pub fn render(path: &str, input: &str, diagnostic: &Diagnostic) -> String {
+    let Position { line, column } = diagnostic.position;
+    let text = input.lines().nth(line.saturating_sub(1)).unwrap_or("");
+    // Columns count bytes, the caret is placed by characters
+    let caret = text
+        .get(..column.saturating_sub(1))
+        .map_or(text.chars().count(), |before| before.chars().count())
+        + column.saturating_sub(1).saturating_sub(text.len());
+
+    let number = line.to_string();
+    let gutter = " ".repeat(number.len());
+
+    format!(
+        "{path}:{line}:{column}: {}\n{gutter} |\n{number} | {text}\n{gutter} | {}^\n",
+        diagnostic.message,
+        " ".repeat(caret)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_test() {
+        let input = "Game 1: 3 blue\nGame 2: 1 blue, 2 purple";
+        let diagnostic = Diagnostic::new(
+            Position {
+                line: 2,
+                column: 19,
+            },
+            &"2:19: expected red, green or blue, found `purple`",
+        );
+
+        assert_eq!(
+            diagnostic.message,
+            "expected red, green or blue, found `purple`"
+        );
+        assert_eq!(
+            render("input.txt", input, &diagnostic),
+            "input.txt:2:19: expected red, green or blue, found `purple`\n  \
+             |\n\
+             2 | Game 2: 1 blue, 2 purple\n  \
+             |                   ^\n"
+        );
+    }
+
+    #[test]
+    fn render_past_end_test() {
+        let diagnostic = Diagnostic::new(
+            Position {
+                line: 10,
+                column: 20,
+            },
+            &"missing ':'",
+        );
+
+        assert_eq!(diagnostic.message, "missing ':'");
+        assert_eq!(
+            render("input.txt", "Card 1: 41 48 83 86\n", &diagnostic),
+            "input.txt:10:20: missing ':'\n   |\n10 | \n   |                    ^\n"
+        );
+    }
+
+    #[test]
+    fn sort_test() {
+        let at = |line, column| Diagnostic::new(Position { line, column }, &"");
+        let mut diagnostics = vec![at(3, 8), at(1, 2), at(3, 1)];
+        sort(&mut diagnostics);
+
+        assert_eq!(diagnostics, [at(1, 2), at(3, 1), at(3, 8)]);
+    }
+}
@@ -0,0 +1,35 @@
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Writes every enabled record to stderr, prefixed with its level and
+/// target so each day's diagnostics can be told apart
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Install the logger: silent by default, debug for `-v` and trace for
+/// `-vv` or more
+pub fn init(verbose: u8) {
+    let level = match verbose {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
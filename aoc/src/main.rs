@@ -0,0 +1,1035 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Instant,
+};
+
+use clap::{Parser, Subcommand};
+use client::{Client, Verdict};
+use dashboard::{Dashboard, InputKind};
+
+mod answers;
+mod bench;
+mod dashboard;
+mod days;
+mod explain;
+mod lint;
+mod logger;
+
+/// Year the puzzles are from, used when downloading inputs
+const YEAR: u16 = 2023;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions runner")]
+struct Cli {
+    /// Log what the solutions are doing to stderr, `-vv` for more detail
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run solutions and print their answers with timings
+    Run {
+        /// Day to run, every day is run when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// Part to run, both parts are run when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Puzzle input file, defaults to dayN/input.txt or the day's
+        /// first example when there's no such file
+        #[arg(short, long, requires = "day")]
+        input: Option<PathBuf>,
+
+        /// Example from dayN/examples to run against
+        #[arg(short, long, conflicts_with = "input")]
+        example: Option<String>,
+
+        /// Use the real puzzle input, downloading it when it isn't cached
+        #[arg(short, long, conflicts_with_all = ["input", "example"])]
+        real: bool,
+
+        /// Solve the input file a line at a time without reading it into
+        /// memory, for days 1, 2, 4 and 7
+        #[arg(long, requires = "input")]
+        stream: bool,
+
+        /// Read the input file as a parsed model in JSON, as printed by
+        /// `dump`, instead of as puzzle text
+        #[arg(long, requires = "input", conflicts_with = "stream")]
+        json: bool,
+    },
+    /// Print the parsed model of a day's input as JSON
+    Dump {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Puzzle input file, defaults as for `run`
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Solve a part and show the steps that led to its answer
+    Explain {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Puzzle input file, defaults as for `run`
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// Example from dayN/examples to explain
+        #[arg(short, long, conflicts_with = "input")]
+        example: Option<String>,
+
+        /// Print the steps as JSON rather than a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Report every problem in a day's input with where it is, failing
+    /// when there are any
+    Lint {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Puzzle input file, defaults as for `run`
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// Example from dayN/examples to check
+        #[arg(short, long, conflicts_with = "input")]
+        example: Option<String>,
+    },
+    /// Check solutions against their naive reference solvers on small
+    /// generated inputs, printing a cut down counterexample when they differ
+    Diff {
+        /// Day to check, every day with a naive solver is checked when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// Part to check, both parts are checked when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Generated inputs to check each part against
+        #[arg(short, long, default_value_t = 2000)]
+        cases: u64,
+
+        /// Seed of the first generated input, each after uses the next seed
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Run every variant of a day's parts, check they give the same answer
+    /// and print how long each took
+    Compare {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Puzzle input file, defaults as for `run`
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// Example from dayN/examples to run the variants against
+        #[arg(short, long, conflicts_with = "input")]
+        example: Option<String>,
+
+        /// Runs of each variant, the median time is shown
+        #[arg(short, long, default_value_t = 10)]
+        runs: usize,
+    },
+    /// Check every day against the answers registry
+    Verify {
+        /// Day to check, every registered day is checked when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// Answers registry, inputs are looked up next to it
+        #[arg(short, long, default_value = "answers.toml")]
+        answers: PathBuf,
+    },
+    /// Time parsing and solving, and compare against earlier runs
+    Bench {
+        #[command(subcommand)]
+        command: BenchCommand,
+    },
+    /// Print a random puzzle input for a day
+    Gen {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Number of lines, games, cards, seed ranges, races, hands or nodes,
+        /// or the width of the schematic
+        #[arg(short, long, default_value_t = 1000)]
+        size: usize,
+
+        /// Seed for the generator, the same seed always gives the same input
+        #[arg(long, default_value_t = 2023)]
+        seed: u64,
+    },
+    /// Show every day's parts with their status, answer and timing, to
+    /// re-run and explain from the keyboard
+    Dashboard,
+    /// Download puzzle inputs into the local cache
+    Fetch {
+        /// Day to download, every day is downloaded when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+    },
+    /// Solve a part against its real input and submit the answer
+    Submit {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Answer to submit instead of solving the part
+        #[arg(short, long)]
+        answer: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BenchCommand {
+    /// Time parse, part 1 and part 2 of each day against its default input
+    /// and a large generated one, adding the timings to the history
+    Run {
+        /// Day to time, every day is timed when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// Runs of each day, the median time of every phase is recorded
+        #[arg(short, long, default_value_t = 10)]
+        runs: usize,
+
+        /// Size of the generated inputs, as for `gen`. Defaults to a size
+        /// that takes each day a while to solve
+        #[arg(short, long)]
+        size: Option<usize>,
+
+        /// Seed for the generated inputs
+        #[arg(long, default_value_t = 2023)]
+        seed: u64,
+
+        /// History file the timings are added to
+        #[arg(long, default_value = "bench-history.json")]
+        history: PathBuf,
+    },
+    /// Compare the latest recorded run with the one before it, failing when
+    /// a phase got slower by more than the threshold
+    Compare {
+        /// Slowdown in percent that counts as a regression
+        #[arg(short, long, default_value_t = 10.0)]
+        threshold: f64,
+
+        #[arg(long, default_value = "bench-history.json")]
+        history: PathBuf,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    logger::init(cli.verbose);
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            example,
+            real,
+            stream,
+            json,
+        } => {
+            if let (Some(day), Some(path), true) = (day, &input, stream) {
+                return run_streamed(day, part, path);
+            }
+
+            let source = match (input, example, real) {
+                (Some(path), _, _) => InputSource::File(path),
+                (_, Some(name), _) => InputSource::Example(name),
+                (_, _, true) => match Client::from_env() {
+                    Ok(client) => InputSource::Real(client),
+                    Err(err) => {
+                        eprintln!("{err}");
+                        return ExitCode::FAILURE;
+                    }
+                },
+                _ => InputSource::Default,
+            };
+            run(day, part, &source, json)
+        }
+        Command::Dump { day, input } => {
+            let source = input.map_or(InputSource::Default, InputSource::File);
+            dump(day, &source)
+        }
+        Command::Explain {
+            day,
+            part,
+            input,
+            example,
+            json,
+        } => {
+            let source = match (input, example) {
+                (Some(path), _) => InputSource::File(path),
+                (_, Some(name)) => InputSource::Example(name),
+                _ => InputSource::Default,
+            };
+            explain(day, part, &source, json)
+        }
+        Command::Lint {
+            day,
+            input,
+            example,
+        } => {
+            let source = match (input, example) {
+                (Some(path), _) => InputSource::File(path),
+                (_, Some(name)) => InputSource::Example(name),
+                _ => InputSource::Default,
+            };
+            lint(day, &source)
+        }
+        Command::Diff {
+            day,
+            part,
+            cases,
+            seed,
+        } => diff(day, part, cases, seed),
+        Command::Compare {
+            day,
+            input,
+            example,
+            runs,
+        } => {
+            let source = match (input, example) {
+                (Some(path), _) => InputSource::File(path),
+                (_, Some(name)) => InputSource::Example(name),
+                _ => InputSource::Default,
+            };
+            compare(day, &source, runs)
+        }
+        Command::Verify { day, answers } => verify(day, &answers),
+        Command::Bench {
+            command:
+                BenchCommand::Run {
+                    day,
+                    runs,
+                    size,
+                    seed,
+                    history,
+                },
+        } => bench_run(day, runs, size, seed, &history),
+        Command::Bench {
+            command: BenchCommand::Compare { threshold, history },
+        } => bench_compare(threshold, &history),
+        Command::Gen { day, size, seed } => match days::generate(day, seed, size) {
+            Some(input) => {
+                print!("{input}");
+                ExitCode::SUCCESS
+            }
+            None => {
+                eprintln!("Day {day} has no solution");
+                ExitCode::FAILURE
+            }
+        },
+        Command::Dashboard => dashboard(),
+        Command::Fetch { day } => fetch(day),
+        Command::Submit { day, part, answer } => submit(day, part, answer),
+    }
+}
+
+/// Where `run` reads each day's input from
+enum InputSource {
+    /// `dayN/input.txt`, or the day's first example without one
+    Default,
+    File(PathBuf),
+    /// Example of that name in `dayN/examples`
+    Example(String),
+    /// Real input from the cache, downloaded when it isn't cached yet
+    Real(Client),
+}
+
+impl InputSource {
+    fn read(&self, day: u8) -> (PathBuf, Result<String, days::BoxError>) {
+        let path = match self {
+            InputSource::Real(client) => {
+                let input = client.input(YEAR, day).map_err(days::BoxError::from);
+                return (client.input_path(YEAR, day), input);
+            }
+            InputSource::File(path) => path.clone(),
+            InputSource::Example(name) => {
+                answers::examples_dir(Path::new("."), day).join(format!("{name}.txt"))
+            }
+            InputSource::Default => {
+                let path = default_input_path(day);
+                let examples = examples::load(&answers::examples_dir(Path::new("."), day));
+                match examples.as_deref() {
+                    Ok([example, ..]) if !path.exists() => {
+                        eprintln!(
+                            "Day {day}: no {}, using the example {} instead",
+                            path.display(),
+                            example
+                                .path
+                                .strip_prefix(".")
+                                .unwrap_or(&example.path)
+                                .display()
+                        );
+                        example.path.clone()
+                    }
+                    _ => path,
+                }
+            }
+        };
+
+        let input = fs::read_to_string(&path).map_err(days::BoxError::from);
+        (path, input)
+    }
+}
+
+/// Run each day against its input from `source`, which is a JSON model
+/// rather than puzzle text when `json` is set
+fn run(day: Option<u8>, part: Option<u8>, source: &InputSource, json: bool) -> ExitCode {
+    let days: Vec<u8> = match day {
+        Some(day) => vec![day],
+        None => days::DAYS.collect(),
+    };
+    let parts: Vec<u8> = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let mut failed = false;
+
+    for day in days {
+        if !days::DAYS.contains(&day) {
+            eprintln!("Day {day} has no solution");
+            failed = true;
+            continue;
+        }
+
+        let (path, input) = source.read(day);
+        let input = match input {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("Day {day}: failed to read {}: {err}", path.display());
+                failed = true;
+                continue;
+            }
+        };
+
+        let day_run = if json {
+            days::run_json(day, &input, &parts)
+        } else {
+            days::run(day, &input, &parts)
+        };
+        let day_run = match day_run {
+            Some(Ok(day_run)) => day_run,
+            Some(Err(err)) => {
+                eprintln!("Day {day}: {}: {err}", path.display());
+                failed = true;
+                continue;
+            }
+            None => continue,
+        };
+
+        println!(
+            "Day {day}: {} (parsed in {:?})",
+            path.strip_prefix(".").unwrap_or(&path).display(),
+            day_run.parse_time
+        );
+
+        for part_run in day_run.parts {
+            match part_run.answer {
+                Ok(Some(answer)) => {
+                    println!("  Part {}: {answer} ({:?})", part_run.part, part_run.time)
+                }
+                Ok(None) => println!("  Part {}: not implemented", part_run.part),
+                Err(err) => {
+                    println!("  Part {}: {}: {err}", part_run.part, path.display());
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn dump(day: u8, source: &InputSource) -> ExitCode {
+    let (path, input) = source.read(day);
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Day {day}: failed to read {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match days::dump(day, &input) {
+        Some(Ok(json)) => {
+            println!("{json}");
+            ExitCode::SUCCESS
+        }
+        Some(Err(err)) => {
+            eprintln!("Day {day}: {}: {err}", path.display());
+            ExitCode::FAILURE
+        }
+        None => {
+            eprintln!("Day {day} has no solution");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn explain(day: u8, part: u8, source: &InputSource, json: bool) -> ExitCode {
+    let (path, input) = source.read(day);
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Day {day}: failed to read {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let trace = match days::explain(day, &input, part) {
+        Some(Ok(trace)) => trace,
+        Some(Err(err)) => {
+            eprintln!("Day {day}: {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+        None => {
+            eprintln!("Day {day} has no solution");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if json {
+        println!("{}", trace.to_json());
+    } else {
+        print_table(&trace.table());
+        println!("Day {day} part {part}: {}", trace.answer);
+    }
+    ExitCode::SUCCESS
+}
+
+fn lint(day: u8, source: &InputSource) -> ExitCode {
+    let (path, input) = source.read(day);
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Day {day}: failed to read {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(diagnostics) = days::lint(day, &input) else {
+        eprintln!("Day {day} has no solution");
+        return ExitCode::FAILURE;
+    };
+
+    let path = path.display().to_string();
+    for diagnostic in &diagnostics {
+        println!("{}", lint::render(&path, &input, diagnostic));
+    }
+
+    match diagnostics.len() {
+        0 => {
+            println!("No problems in {path}");
+            ExitCode::SUCCESS
+        }
+        1 => {
+            println!("1 problem in {path}");
+            ExitCode::FAILURE
+        }
+        count => {
+            println!("{count} problems in {path}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn diff(day: Option<u8>, part: Option<u8>, cases: u64, seed: u64) -> ExitCode {
+    let days = match day {
+        Some(day) => vec![day],
+        None => days::NAIVE_DAYS.to_vec(),
+    };
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let mut failed = false;
+
+    for day in days {
+        for &part in &parts {
+            match days::differential(day, part, seed, cases) {
+                Some(Ok(())) => println!("Day {day} part {part}: agrees on {cases} inputs"),
+                Some(Err(counterexample)) => {
+                    println!("Day {day} part {part}: {counterexample}");
+                    failed = true;
+                }
+                None => {
+                    eprintln!("Day {day} has no naive solver");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn compare(day: u8, source: &InputSource, runs: usize) -> ExitCode {
+    let (path, input) = source.read(day);
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Day {day}: failed to read {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(variant_runs) = days::compare(day, &input, runs) else {
+        eprintln!("Day {day} has no variants to compare");
+        return ExitCode::FAILURE;
+    };
+
+    let mut rows = vec![[
+        "Part".to_string(),
+        "Variant".to_string(),
+        "Answer".to_string(),
+        "Time".to_string(),
+    ]];
+    for run in &variant_runs {
+        let (answer, time) = match (&run.skipped, &run.answer) {
+            (Some(reason), _) => (format!("skipped: {reason}"), "-".to_string()),
+            (None, Ok(answer)) => (answer.to_string(), format!("{:.2?}", run.time)),
+            (None, Err(err)) => (format!("error: {err}"), format!("{:.2?}", run.time)),
+        };
+        rows.push([run.part.to_string(), run.name.to_string(), answer, time]);
+    }
+    print_table(&rows);
+
+    let disagreements = bench::disagreements(&variant_runs);
+    for part in &disagreements {
+        println!("Part {part}: the variants disagree");
+    }
+
+    if disagreements.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn run_streamed(day: u8, part: Option<u8>, path: &Path) -> ExitCode {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let mut failed = false;
+
+    println!("Day {day} (streamed from {})", path.display());
+
+    for part in parts {
+        let start = Instant::now();
+        match days::stream(day, path, part) {
+            Some(Ok(answer)) => println!("  Part {part}: {answer} ({:?})", start.elapsed()),
+            Some(Err(err)) => {
+                println!("  Part {part}: {}: {err}", path.display());
+                failed = true;
+            }
+            None => {
+                eprintln!("Day {day} can't be streamed");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn verify(day: Option<u8>, registry: &Path) -> ExitCode {
+    let known_answers = match answers::load(registry) {
+        Ok(known_answers) => known_answers,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut rows = vec![[
+        "Day".to_string(),
+        "Input".to_string(),
+        "Part".to_string(),
+        "Expected".to_string(),
+        "Actual".to_string(),
+        "Status".to_string(),
+    ]];
+    let mut failed = false;
+
+    for known in known_answers
+        .iter()
+        .filter(|known| day.is_none_or(|day| day == known.day))
+    {
+        let (actual, status) = match check(known) {
+            Ok(actual) if actual == known.answer => (actual, "pass"),
+            Ok(actual) => (actual, "regression"),
+            Err(err) => (err.to_string(), "fail"),
+        };
+        failed |= status != "pass";
+
+        rows.push([
+            known.day.to_string(),
+            known.input.clone(),
+            known.part.to_string(),
+            known.answer.clone(),
+            actual,
+            status.to_string(),
+        ]);
+    }
+
+    print_table(&rows);
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench_run(
+    day: Option<u8>,
+    runs: usize,
+    size: Option<usize>,
+    seed: u64,
+    history: &Path,
+) -> ExitCode {
+    let days: Vec<u8> = match day {
+        Some(day) => vec![day],
+        None => days::DAYS.collect(),
+    };
+
+    let mut rows = vec![[
+        "Day".to_string(),
+        "Input".to_string(),
+        "Parse".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+    ]];
+    let mut timings = vec![];
+    let mut failed = false;
+
+    for day in days {
+        if !days::DAYS.contains(&day) {
+            eprintln!("Day {day} has no solution");
+            failed = true;
+            continue;
+        }
+
+        let (path, input) = InputSource::Default.read(day);
+        let name = path
+            .strip_prefix(".")
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let mut inputs = vec![(name, input)];
+        let size = size.unwrap_or(bench::generated_size(day));
+        if let Some(generated) = days::generate(day, seed, size) {
+            inputs.push((format!("generated-{size}"), Ok(generated)));
+        }
+
+        for (name, input) in inputs {
+            let measured = input.and_then(|input| bench::measure(day, &input, runs));
+            let measured = match measured {
+                Ok(measured) => measured,
+                Err(err) => {
+                    eprintln!("Day {day}: {name}: {err}");
+                    failed = true;
+                    continue;
+                }
+            };
+            if let Some(err) = measured.error {
+                eprintln!("Day {day}: {name}: {err}");
+            }
+
+            let mut row = [
+                day.to_string(),
+                name.clone(),
+                "-".to_string(),
+                "-".to_string(),
+                "-".to_string(),
+            ];
+            for (phase, time) in measured.timings {
+                let column = match phase {
+                    "parse" => 2,
+                    "part1" => 3,
+                    _ => 4,
+                };
+                row[column] = format!("{time:.2?}");
+                timings.push(bench::Timing {
+                    day,
+                    input: name.clone(),
+                    phase: phase.to_string(),
+                    nanos: u64::try_from(time.as_nanos()).unwrap_or(u64::MAX),
+                });
+            }
+            rows.push(row);
+        }
+    }
+
+    print_table(&rows);
+
+    match bench::record(history, bench::new_record(timings)) {
+        Ok(count) => println!("Recorded run {count} in {}", history.display()),
+        Err(err) => {
+            eprintln!("{err}");
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench_compare(threshold: f64, history: &Path) -> ExitCode {
+    let records = match bench::load_history(history) {
+        Ok(records) => records,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let [.., previous, latest] = records.as_slice() else {
+        eprintln!(
+            "{} needs at least two recorded runs to compare",
+            history.display()
+        );
+        return ExitCode::FAILURE;
+    };
+    if previous.parallel != latest.parallel {
+        eprintln!("Only one of the runs was built with the parallel feature");
+    }
+
+    let mut rows = vec![[
+        "Day".to_string(),
+        "Input".to_string(),
+        "Phase".to_string(),
+        "Previous".to_string(),
+        "Latest".to_string(),
+        "Change".to_string(),
+        "Status".to_string(),
+    ]];
+    let mut failed = false;
+
+    for change in bench::compare(previous, latest) {
+        let status = match change.percent() {
+            None => "new",
+            Some(_) if change.is_regression(threshold) => "regression",
+            Some(percent) if percent < -threshold => "faster",
+            Some(_) => "ok",
+        };
+        failed |= status == "regression";
+
+        rows.push([
+            change.day.to_string(),
+            change.input.clone(),
+            change.phase.clone(),
+            change
+                .previous
+                .map_or("-".to_string(), |previous| format!("{previous:.2?}")),
+            format!("{:.2?}", change.latest),
+            change
+                .percent()
+                .map_or("-".to_string(), |percent| format!("{percent:+.1}%")),
+            status.to_string(),
+        ]);
+    }
+
+    print_table(&rows);
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Solve the part a known answer is registered for
+fn check(known: &answers::KnownAnswer) -> Result<String, days::BoxError> {
+    let input = fs::read_to_string(&known.path)
+        .map_err(|err| format!("failed to read {}: {err}", known.path.display()))?;
+
+    Ok(days::solve(known.day, &input, known.part)?.to_string())
+}
+
+/// Print rows as left aligned columns, the first row being the header
+fn print_table<R: AsRef<[String]>>(rows: &[R]) {
+    let mut widths = vec![];
+    for row in rows {
+        for (idx, cell) in row.as_ref().iter().enumerate() {
+            match widths.get_mut(idx) {
+                Some(width) => *width = cell.len().max(*width),
+                None => widths.push(cell.len()),
+            }
+        }
+    }
+
+    for row in rows {
+        let line = row
+            .as_ref()
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    }
+}
+
+fn dashboard() -> ExitCode {
+    // without a session the real inputs are read from dayN/input.txt
+    let client = Client::from_env().ok();
+    let load = |day: u8, kind: InputKind| {
+        let (path, input) = match (kind, &client) {
+            (InputKind::Example, _) => {
+                let dir = answers::examples_dir(Path::new("."), day);
+                match examples::load(&dir).as_deref() {
+                    Ok([example, ..]) => {
+                        (example.path.clone(), example.input().map_err(Into::into))
+                    }
+                    Ok([]) => (dir, Err("no examples".into())),
+                    Err(err) => (dir, Err(err.to_string().into())),
+                }
+            }
+            (InputKind::Real, Some(client)) => (
+                client.input_path(YEAR, day),
+                client.input(YEAR, day).map_err(days::BoxError::from),
+            ),
+            (InputKind::Real, None) => {
+                let path = default_input_path(day);
+                let input = fs::read_to_string(&path).map_err(Into::into);
+                (path, input)
+            }
+        };
+        let name = path
+            .strip_prefix(".")
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+
+        (name, input)
+    };
+
+    let mut dashboard = Dashboard::new(load);
+    let result = ratatui::run(|terminal| dashboard::run(terminal, &mut dashboard));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(day: Option<u8>) -> ExitCode {
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let days: Vec<u8> = match day {
+        Some(day) => vec![day],
+        None => days::DAYS.collect(),
+    };
+
+    let mut failed = false;
+
+    for day in days {
+        match client.input(YEAR, day) {
+            Ok(_) => println!("Day {day}: {}", client.input_path(YEAR, day).display()),
+            Err(err) => {
+                eprintln!("Day {day}: {err}");
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn submit(day: u8, part: u8, answer: Option<String>) -> ExitCode {
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => match solve(&client, day, part) {
+            Ok(answer) => answer,
+            Err(err) => {
+                eprintln!("Day {day} part {part}: {err}");
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+
+    match client.submit(YEAR, day, part, &answer) {
+        Ok(verdict) => {
+            println!("Day {day} part {part}: {answer} is {verdict}");
+            if verdict == Verdict::Correct || verdict == Verdict::AlreadySolved {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(err) => {
+            eprintln!("Day {day} part {part}: {answer} not submitted: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Answer to a part against its real input
+fn solve(client: &Client, day: u8, part: u8) -> Result<String, days::BoxError> {
+    let input = client.input(YEAR, day)?;
+    Ok(days::solve(day, &input, part)?.to_string())
+}
+
+fn default_input_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("day{day}")).join("input.txt")
+}
@@ -0,0 +1,60 @@
+use std::{error, fmt::Display, io, time::Duration};
+
+use crate::Verdict;
+
+/// Error from talking to the Advent of Code website
+#[derive(Debug)]
+pub enum Error {
+    /// No session token in the environment or the config file
+    MissingSession,
+    /// The website answered with an unsuccessful status code
+    Status { url: String, status: u16 },
+    /// The request couldn't be sent or the response couldn't be read
+    Http(ureq::Error),
+    /// The answer, or a bound that rules it out, was already rejected
+    Rejected { answer: String, verdict: Verdict },
+    /// The website doesn't accept answers again until the cooldown is over
+    Cooldown { remaining: Duration },
+    /// The page returned after submitting didn't contain a verdict
+    UnknownResponse,
+    /// The cache or config file couldn't be read or written
+    Io(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session token, set {} or write it to the config file",
+                crate::SESSION_ENV
+            ),
+            Error::Status { url, status } => write!(f, "{url} answered with status {status}"),
+            Error::Http(err) => write!(f, "request failed: {err}"),
+            Error::Rejected { answer, verdict } => {
+                write!(f, "ruled out by {answer}, which was {verdict}")
+            }
+            Error::Cooldown { remaining } => {
+                write!(f, "wait {}s before submitting again", remaining.as_secs())
+            }
+            Error::UnknownResponse => write!(f, "no verdict in the response page"),
+            Error::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Http(err) => Some(err),
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
@@ -0,0 +1,69 @@
+use std::{fs, path::PathBuf};
+
+use crate::{Client, Error};
+
+impl Client {
+    /// Where the input for a puzzle is cached
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.day_dir(year, day).join("input.txt")
+    }
+
+    /// Puzzle input for a day, downloaded only when it isn't cached yet
+    pub fn input(&self, year: u16, day: u8) -> Result<String, Error> {
+        let path = self.input_path(year, day);
+        if path.exists() {
+            return Ok(fs::read_to_string(path)?);
+        }
+
+        let input = self.get(&format!("/{year}/day/{day}/input"))?;
+
+        // write to a temporary file first so an interrupted download
+        // never leaves a partial input in the cache
+        fs::create_dir_all(self.day_dir(year, day))?;
+        let partial = path.with_extension("part");
+        fs::write(&partial, &input)?;
+        fs::rename(partial, &path)?;
+
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stub::{temp_dir, StubServer};
+
+    use super::*;
+
+    #[test]
+    fn input_is_downloaded_once_test() {
+        let server = StubServer::start(|_| (200, "seeds: 79 14\n".to_string()));
+        let cache_dir = temp_dir("input_is_downloaded_once");
+        let client = Client::new("abc123", &cache_dir).with_base_url(server.url());
+
+        assert_eq!(client.input(2023, 5).unwrap(), "seeds: 79 14\n");
+        assert_eq!(client.input(2023, 5).unwrap(), "seeds: 79 14\n");
+        assert_eq!(
+            fs::read_to_string(cache_dir.join("2023").join("day5").join("input.txt")).unwrap(),
+            "seeds: 79 14\n"
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2023/day/5/input");
+        assert_eq!(requests[0].header("cookie"), Some("session=abc123"));
+        assert_eq!(requests[0].header("user-agent"), Some(crate::USER_AGENT));
+    }
+
+    #[test]
+    fn failed_download_is_not_cached_test() {
+        let server = StubServer::start(|_| (404, "Not Found".to_string()));
+        let cache_dir = temp_dir("failed_download_is_not_cached");
+        let client = Client::new("abc123", &cache_dir).with_base_url(server.url());
+
+        let err = client.input(2023, 26).unwrap_err();
+
+        assert!(matches!(err, Error::Status { status: 404, .. }));
+        assert!(!client.input_path(2023, 26).exists());
+    }
+}
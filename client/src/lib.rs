@@ -0,0 +1,150 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use ureq::Agent;
+
+mod error;
+mod fetch;
+#[cfg(test)]
+mod stub;
+mod submit;
+
+pub use error::Error;
+pub use submit::Verdict;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Sent with every request, as the website asks automated tools to
+/// identify themselves
+pub const USER_AGENT: &str = "github.com/OBBO67/aoc-2023 puzzle runner";
+
+/// Environment variable holding the session cookie value
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Environment variable overriding where inputs are cached
+pub const CACHE_DIR_ENV: &str = "AOC_CACHE_DIR";
+
+/// Client for a single Advent of Code account, caching everything it
+/// downloads on disk
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+}
+
+impl Client {
+    pub fn new(session: impl Into<String>, cache_dir: impl Into<PathBuf>) -> Self {
+        let agent = Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .http_status_as_error(false)
+            .build()
+            .into();
+
+        Client {
+            agent,
+            base_url: BASE_URL.to_string(),
+            session: session.into(),
+            cache_dir: cache_dir.into(),
+        }
+    }
+
+    /// Client using the session token and cache directory from the
+    /// environment or config file
+    pub fn from_env() -> Result<Self, Error> {
+        Ok(Client::new(load_session()?, default_cache_dir()))
+    }
+
+    /// Send requests somewhere other than the real website
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Directory holding everything cached for a single puzzle
+    fn day_dir(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day{day}"))
+    }
+
+    fn get(&self, path: &str) -> Result<String, Error> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .call();
+
+        read_body(url, response)
+    }
+
+    fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, Error> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form(form.iter().copied());
+
+        read_body(url, response)
+    }
+}
+
+fn read_body(
+    url: String,
+    response: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+) -> Result<String, Error> {
+    let mut response = response.map_err(Error::Http)?;
+
+    let status = response.status().as_u16();
+    if status != 200 {
+        return Err(Error::Status { url, status });
+    }
+
+    response.body_mut().read_to_string().map_err(Error::Http)
+}
+
+/// Read the session token from [`SESSION_ENV`], falling back to the
+/// `session` file in the config directory
+pub fn load_session() -> Result<String, Error> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().to_string());
+        }
+    }
+
+    let path = config_dir().ok_or(Error::MissingSession)?.join("session");
+    match fs::read_to_string(path) {
+        Ok(session) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+        _ => Err(Error::MissingSession),
+    }
+}
+
+/// `$XDG_CONFIG_HOME/aoc`, or `~/.config/aoc` when that isn't set
+pub fn config_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+
+    Some(base.join("aoc"))
+}
+
+/// [`CACHE_DIR_ENV`], or `~/.cache/aoc` when that isn't set
+pub fn default_cache_dir() -> PathBuf {
+    if let Some(dir) = env::var_os(CACHE_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+
+    match env::var_os("HOME") {
+        Some(home) => PathBuf::from(home).join(".cache").join("aoc"),
+        None => PathBuf::from(".aoc-cache"),
+    }
+}
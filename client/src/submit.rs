@@ -0,0 +1,381 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{Client, Error};
+
+/// How the website judged a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way
+    Incorrect,
+    /// Submitted too soon after the last answer, nothing was judged
+    Wait(Duration),
+    /// The part was already solved, nothing was judged
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Read the verdict out of the page returned after submitting
+    pub fn from_page(page: &str) -> Option<Verdict> {
+        let text = article_text(page);
+
+        if text.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if text.contains("You gave an answer too recently") {
+            Some(Verdict::Wait(parse_left_to_wait(&text).unwrap_or_default()))
+        } else if text.contains("Did you already complete it") {
+            Some(Verdict::AlreadySolved)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Some(Verdict::TooHigh)
+            } else if text.contains("your answer is too low") {
+                Some(Verdict::TooLow)
+            } else {
+                Some(Verdict::Incorrect)
+            }
+        } else {
+            None
+        }
+    }
+
+    pub fn is_rejection(&self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+
+    /// Name stored in the submission history
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::Wait(_) => "wait",
+            Verdict::AlreadySolved => "already-solved",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        match name {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "incorrect" => Some(Verdict::Incorrect),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::Wait(wait) => write!(f, "wait {}s before submitting", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Text of the `<article>` holding the response, with tags stripped
+fn article_text(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// Cooldown from "You have 4m 32s left to wait"
+fn parse_left_to_wait(text: &str) -> Option<Duration> {
+    let (before, _) = text.split_once(" left to wait")?;
+    let (_, wait) = before.rsplit_once("You have ")?;
+
+    wait.split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.len().checked_sub(1)?);
+            let number = number.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(number * 60 * 60),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Cooldown after a wrong answer, from "please wait 5 minutes" or
+/// "please wait one minute"
+fn parse_wrong_answer_wait(text: &str) -> Option<Duration> {
+    let text = text.to_lowercase();
+    let (_, wait) = text.split_once("please wait ")?;
+    let mut words = wait.split_whitespace();
+    let minutes = match words.next()? {
+        "one" => 1,
+        number => number.parse::<u64>().ok()?,
+    };
+
+    Some(Duration::from_secs(minutes * 60))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or_default()
+}
+
+impl Client {
+    /// Every answer submitted for a puzzle that got a verdict, one
+    /// `part answer verdict` line each
+    fn submissions_path(&self, year: u16, day: u8) -> PathBuf {
+        self.day_dir(year, day).join("submissions.txt")
+    }
+
+    /// Unix time before which nothing may be submitted, shared by every
+    /// puzzle as the website limits the whole account
+    fn cooldown_path(&self) -> PathBuf {
+        self.cache_dir.join("cooldown")
+    }
+
+    /// Answers submitted for a part along with their verdicts
+    pub fn submissions(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+    ) -> Result<Vec<(String, Verdict)>, Error> {
+        let history = match fs::read_to_string(self.submissions_path(year, day)) {
+            Ok(history) => history,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+
+        let part = part.to_string();
+        Ok(history
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                let (line_part, answer, verdict) = (fields.next()?, fields.next()?, fields.next()?);
+                (line_part == part).then_some((answer.to_string(), Verdict::from_name(verdict)?))
+            })
+            .collect())
+    }
+
+    /// Time left before another answer may be submitted
+    pub fn cooldown(&self) -> Result<Option<Duration>, Error> {
+        let until = match fs::read_to_string(self.cooldown_path()) {
+            Ok(until) => until.trim().parse::<u64>().unwrap_or_default(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(until
+            .checked_sub(now())
+            .filter(|&left| left > 0)
+            .map(Duration::from_secs))
+    }
+
+    /// Submit an answer for a part, refusing to send anything that was
+    /// already rejected or that the cooldown doesn't allow yet
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Verdict, Error> {
+        let submissions = self.submissions(year, day, part)?;
+        if submissions
+            .iter()
+            .any(|(_, verdict)| *verdict == Verdict::Correct)
+        {
+            return Ok(Verdict::AlreadySolved);
+        }
+        if let Some((rejected, verdict)) = known_rejection(&submissions, answer) {
+            return Err(Error::Rejected {
+                answer: rejected.to_string(),
+                verdict: verdict.clone(),
+            });
+        }
+        if let Some(remaining) = self.cooldown()? {
+            return Err(Error::Cooldown { remaining });
+        }
+
+        let level = part.to_string();
+        let page = self.post_form(
+            &format!("/{year}/day/{day}/answer"),
+            &[("level", &level), ("answer", answer)],
+        )?;
+        let verdict = Verdict::from_page(&page).ok_or(Error::UnknownResponse)?;
+
+        let cooldown = match &verdict {
+            Verdict::Wait(wait) => Some(*wait),
+            verdict if verdict.is_rejection() => parse_wrong_answer_wait(&article_text(&page)),
+            _ => None,
+        };
+        if let Some(cooldown) = cooldown {
+            fs::create_dir_all(&self.cache_dir)?;
+            fs::write(
+                self.cooldown_path(),
+                (now() + cooldown.as_secs()).to_string(),
+            )?;
+        }
+
+        if verdict == Verdict::Correct || verdict.is_rejection() {
+            fs::create_dir_all(self.day_dir(year, day))?;
+            let mut history =
+                fs::read_to_string(self.submissions_path(year, day)).unwrap_or_default();
+            history.push_str(&format!("{part}\t{answer}\t{}\n", verdict.name()));
+            fs::write(self.submissions_path(year, day), history)?;
+        }
+
+        Ok(verdict)
+    }
+}
+
+/// An earlier rejection that rules out `answer`, either the same answer or
+/// a bound that a numeric answer is beyond
+fn known_rejection<'a>(
+    submissions: &'a [(String, Verdict)],
+    answer: &str,
+) -> Option<&'a (String, Verdict)> {
+    let number = answer.parse::<i128>().ok();
+
+    submissions.iter().find(|(rejected, verdict)| {
+        if !verdict.is_rejection() {
+            return false;
+        }
+        if rejected == answer {
+            return true;
+        }
+
+        match (number, rejected.parse::<i128>().ok(), verdict) {
+            (Some(number), Some(bound), Verdict::TooHigh) => number >= bound,
+            (Some(number), Some(bound), Verdict::TooLow) => number <= bound,
+            _ => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stub::{temp_dir, StubServer};
+
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn verdict_from_page_test() {
+        assert_eq!(
+            Verdict::from_page(&page(
+                "That's the right answer! You are <span>one gold star</span> closer."
+            )),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::from_page(&page(
+                "That's not the right answer; your answer is too high."
+            )),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::from_page(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            Verdict::from_page(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait."
+            )),
+            Some(Verdict::Wait(Duration::from_secs(272)))
+        );
+        assert_eq!(
+            Verdict::from_page(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Some(Verdict::AlreadySolved)
+        );
+        assert_eq!(Verdict::from_page("<html></html>"), None);
+    }
+
+    #[test]
+    fn rejected_answers_are_not_resubmitted_test() {
+        let server = StubServer::start(|_| {
+            (
+                200,
+                page("That's not the right answer; your answer is too high.  Please wait one minute before trying again."),
+            )
+        });
+        let client = Client::new("abc123", temp_dir("rejected_answers_are_not_resubmitted"))
+            .with_base_url(server.url());
+
+        assert_eq!(client.submit(2023, 6, 1, "1000").unwrap(), Verdict::TooHigh);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2023/day/6/answer");
+        assert_eq!(requests[0].body, "level=1&answer=1000");
+
+        // the same answer and anything above it are known to be wrong
+        for answer in ["1000", "1200"] {
+            assert!(matches!(
+                client.submit(2023, 6, 1, answer),
+                Err(Error::Rejected {
+                    verdict: Verdict::TooHigh,
+                    ..
+                })
+            ));
+        }
+        // anything else still has to wait out the minute
+        assert!(matches!(
+            client.submit(2023, 6, 1, "900"),
+            Err(Error::Cooldown { .. })
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn wait_starts_cooldown_test() {
+        let server = StubServer::start(|_| {
+            (
+                200,
+                page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 30s left to wait."),
+            )
+        });
+        let client =
+            Client::new("abc123", temp_dir("wait_starts_cooldown")).with_base_url(server.url());
+
+        assert_eq!(
+            client.submit(2023, 7, 2, "5905").unwrap(),
+            Verdict::Wait(Duration::from_secs(30))
+        );
+        assert!(client.cooldown().unwrap().is_some());
+        assert!(client.submissions(2023, 7, 2).unwrap().is_empty());
+        assert_eq!(server.requests().len(), 1);
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt::Display;
+
+/// A puzzle answer, wide enough to hold the result of any part
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    Signed(i128),
+    Unsigned(u128),
+    Text(String),
+}
+
+impl Answer {
+    /// Widen a numeric answer to `i128`, `None` for text answers or
+    /// unsigned answers that don't fit
+    fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Signed(value) => Some(*value),
+            Answer::Unsigned(value) => i128::try_from(*value).ok(),
+            Answer::Text(_) => None,
+        }
+    }
+
+    /// Widen a numeric answer to `u128`, `None` for text answers or
+    /// negative answers
+    fn as_u128(&self) -> Option<u128> {
+        match self {
+            Answer::Signed(value) => u128::try_from(*value).ok(),
+            Answer::Unsigned(value) => Some(*value),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    /// Numeric answers are equal when they hold the same value,
+    /// regardless of signedness
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Signed(a), _) => other.as_i128() == Some(*a),
+            (Answer::Unsigned(a), _) => other.as_u128() == Some(*a),
+            (Answer::Text(_), _) => false,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(value) => write!(f, "{value}"),
+            Answer::Unsigned(value) => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($variant:ident, $wide:ty, $as_wide:ident, $($int:ty),+) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(value: $int) -> Self {
+                    Answer::$variant(<$wide>::from(value))
+                }
+            }
+
+            impl PartialEq<$int> for Answer {
+                fn eq(&self, other: &$int) -> bool {
+                    self.$as_wide() == Some(<$wide>::from(*other))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_int!(Signed, i128, as_i128, i8, i16, i32, i64, i128);
+impl_from_int!(Unsigned, u128, as_u128, u8, u16, u32, u64, u128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Unsigned(value as u128)
+    }
+}
+
+/// Unsigned when it fits in a `u128`, otherwise its digits as text
+#[cfg(feature = "bigint")]
+impl From<num_bigint::BigUint> for Answer {
+    fn from(value: num_bigint::BigUint) -> Self {
+        match u128::try_from(&value) {
+            Ok(value) => Answer::Unsigned(value),
+            Err(_) => Answer::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, Answer::Text(text) if text == other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equality_ignores_signedness_test() {
+        assert_eq!(Answer::from(54877_i32), Answer::from(54877_u64));
+        assert_ne!(Answer::from(-1_i64), Answer::from(u128::MAX));
+        assert_ne!(Answer::from(6_u32), Answer::from("6"));
+    }
+
+    #[test]
+    fn equality_against_expected_test() {
+        assert_eq!(Answer::from(71503_u64), 71503);
+        assert_eq!(Answer::from(-35_i64), -35_i64);
+        assert_eq!(Answer::from("AAA"), "AAA");
+        assert_ne!(Answer::from(35_i64), "35");
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(Answer::from(-46_i64).to_string(), "-46");
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from("ZZZ").to_string(), "ZZZ");
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn bigint_test() {
+        let big = num_bigint::BigUint::from(u128::MAX) + 1u32;
+
+        assert_eq!(Answer::from(num_bigint::BigUint::from(35u32)), 35);
+        assert_eq!(Answer::from(big), "340282366920938463463374607431768211456");
+    }
+}
@@ -0,0 +1,205 @@
+use std::fmt::Display;
+
+use crate::{Answer, Rng};
+
+/// Input on which a solver and its naive reference disagree, cut down to
+/// as few lines, words and as small numbers as still disagree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counterexample {
+    /// Seed of the generated input the counterexample was cut down from
+    pub seed: u64,
+    pub input: String,
+    /// Answer, or error message, from the naive reference
+    pub naive: Result<Answer, String>,
+    /// Answer, or error message, from the solver being checked
+    pub solver: Result<Answer, String>,
+}
+
+impl Display for Counterexample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let outcome = |result: &Result<Answer, String>| match result {
+            Ok(answer) => answer.to_string(),
+            Err(message) => format!("error `{message}`"),
+        };
+
+        writeln!(
+            f,
+            "naive solver gives {} but the solver gives {}, cut down from seed {}:",
+            outcome(&self.naive),
+            outcome(&self.solver),
+            self.seed
+        )?;
+        write!(f, "{}", self.input)
+    }
+}
+
+/// Compare `solver` to its `naive` reference on `cases` inputs from
+/// `generate`, the first from `seed` and each after from the next seed.
+/// The first input they disagree on is minimized into a counterexample.
+/// Errors count as agreeing when their messages are the same
+pub fn differential<E: Display>(
+    seed: u64,
+    cases: u64,
+    generate: impl Fn(&mut Rng) -> String,
+    naive: impl Fn(&str) -> Result<Answer, E>,
+    solver: impl Fn(&str) -> Result<Answer, E>,
+) -> Result<(), Counterexample> {
+    let outcomes = |input: &str| {
+        let outcome = |result: Result<Answer, E>| result.map_err(|err| err.to_string());
+        (outcome(naive(input)), outcome(solver(input)))
+    };
+    let disagree = |input: &str| {
+        let (naive, solver) = outcomes(input);
+        naive != solver
+    };
+
+    for seed in seed..seed.saturating_add(cases) {
+        let input = generate(&mut Rng::new(seed));
+        if disagree(&input) {
+            let input = minimize(&input, disagree);
+            let (naive, solver) = outcomes(&input);
+            return Err(Counterexample {
+                seed,
+                input,
+                naive,
+                solver,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Cut `input` down for as long as it stays `interesting`: drop runs of
+/// lines, then runs of words within a line, then make each number
+/// smaller, until none of those keep it interesting
+pub fn minimize(input: &str, interesting: impl Fn(&str) -> bool) -> String {
+    let mut lines: Vec<String> = input.split('\n').map(String::from).collect();
+
+    loop {
+        let mut smaller = drop_runs(&mut lines, |lines| interesting(&lines.join("\n")));
+
+        for line in 0..lines.len() {
+            // an empty line has one empty word, which can't be dropped
+            if lines[line].is_empty() {
+                continue;
+            }
+
+            let mut words: Vec<String> = lines[line].split(' ').map(String::from).collect();
+            let with_words = |lines: &[String], words: &[String]| {
+                let mut candidate = lines.to_vec();
+                candidate[line] = words.join(" ");
+                candidate.join("\n")
+            };
+
+            smaller |= drop_runs(&mut words, |words| interesting(&with_words(&lines, words)));
+
+            for word in 0..words.len() {
+                let Ok(number) = words[word].parse::<u64>() else {
+                    continue;
+                };
+
+                for shrunk in [0, 1, number / 2, number.saturating_sub(1)] {
+                    if shrunk >= number {
+                        continue;
+                    }
+
+                    let mut candidate = words.clone();
+                    candidate[word] = shrunk.to_string();
+                    if interesting(&with_words(&lines, &candidate)) {
+                        words = candidate;
+                        smaller = true;
+                        break;
+                    }
+                }
+            }
+
+            lines[line] = words.join(" ");
+        }
+
+        if !smaller {
+            return lines.join("\n");
+        }
+    }
+}
+
+/// Drop runs of `items`, the longest runs first, for as long as what's
+/// left stays `interesting`. True when anything was dropped
+fn drop_runs(items: &mut Vec<String>, interesting: impl Fn(&[String]) -> bool) -> bool {
+    let mut dropped = false;
+    let mut run = items.len();
+
+    while run > 0 {
+        let mut start = 0;
+        while start + run <= items.len() {
+            let mut candidate = items.clone();
+            candidate.drain(start..start + run);
+            if interesting(&candidate) {
+                *items = candidate;
+                dropped = true;
+            } else {
+                start += 1;
+            }
+        }
+        run /= 2;
+    }
+
+    dropped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sum of every number in the input
+    fn sum(input: &str) -> Result<Answer, String> {
+        let numbers = input
+            .split_whitespace()
+            .map(|word| word.parse::<u64>().map_err(|err| err.to_string()))
+            .collect::<Result<Vec<u64>, String>>()?;
+
+        Ok(numbers.iter().sum::<u64>().into())
+    }
+
+    /// Sum that forgets any number past 100
+    fn buggy_sum(input: &str) -> Result<Answer, String> {
+        let numbers = input
+            .split_whitespace()
+            .map(|word| word.parse::<u64>().map_err(|err| err.to_string()))
+            .collect::<Result<Vec<u64>, String>>()?;
+
+        Ok(numbers.iter().filter(|&&n| n <= 100).sum::<u64>().into())
+    }
+
+    fn numbers(rng: &mut Rng) -> String {
+        (0..5)
+            .map(|_| {
+                let line: Vec<String> = (0..4).map(|_| rng.range(0..120).to_string()).collect();
+                line.join(" ")
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn differential_test() {
+        assert_eq!(differential(0, 100, numbers, sum, sum), Ok(()));
+
+        let counterexample = differential(0, 100, numbers, sum, buggy_sum).unwrap_err();
+        assert_eq!(counterexample.input, "101");
+        assert_eq!(counterexample.naive, Ok(Answer::from(101)));
+        assert_eq!(counterexample.solver, Ok(Answer::from(0)));
+        assert!(counterexample
+            .to_string()
+            .starts_with("naive solver gives 101 but the solver gives 0, cut down from seed"));
+    }
+
+    #[test]
+    fn minimize_test() {
+        let has_pair = |input: &str| input.lines().any(|line| line.contains("7 7"));
+
+        assert_eq!(minimize("1 2\n3 7 7 9\n7 8", has_pair), "7 7");
+        assert_eq!(minimize("1 2", |_| false), "1 2");
+        assert_eq!(minimize("1\n\n2", |input| input.contains('\n')), "\n");
+    }
+}
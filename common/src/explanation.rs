@@ -0,0 +1,18 @@
+use crate::Answer;
+
+/// Answer to a part along with the steps that led to it, such as each
+/// number that was added up, for checking where a wrong answer came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation<T> {
+    pub answer: Answer,
+    pub steps: Vec<T>,
+}
+
+impl<T> Explanation<T> {
+    pub fn new(answer: impl Into<Answer>, steps: Vec<T>) -> Self {
+        Explanation {
+            answer: answer.into(),
+            steps,
+        }
+    }
+}
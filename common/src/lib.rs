@@ -0,0 +1,44 @@
+use std::error::Error;
+
+mod answer;
+mod differential;
+mod explanation;
+pub mod par;
+mod position;
+mod reader;
+mod rng;
+mod variant;
+mod wide;
+
+pub use answer::Answer;
+pub use differential::{differential, minimize, Counterexample};
+pub use explanation::Explanation;
+pub use position::Position;
+pub use reader::{for_each_line, ReadError};
+pub use rng::Rng;
+pub use variant::Variant;
+#[cfg(feature = "serde")]
+pub use wide::decimal as wide_decimal;
+pub use wide::{checked_add, checked_mul, checked_pow, checked_product, checked_sum, sqrt, Wide};
+
+/// A day's puzzle solution, split into a parse step that produces the
+/// typed puzzle input and the two parts that solve over it
+pub trait Solution {
+    /// Day of the puzzle, 1 to 25
+    const DAY: u8;
+
+    /// Parsed puzzle input shared by both parts
+    type Input;
+
+    /// Error for input that doesn't match the puzzle format
+    type Error: Error + Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error>;
+
+    fn part1(input: &Self::Input) -> Result<Answer, Self::Error>;
+
+    /// Returns `Ok(None)` while part 2 hasn't been solved yet
+    fn part2(_input: &Self::Input) -> Result<Option<Answer>, Self::Error> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,83 @@
+//! Per-item pipelines that run on rayon's thread pool with the `parallel`
+//! feature, and in order on the current thread without it. Either way
+//! the results are the same
+
+use std::cmp::Ordering;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Map `f` over `items`
+pub fn map<T: Sync, U: Send>(items: &[T], f: impl Fn(&T) -> U + Sync + Send) -> Vec<U> {
+    #[cfg(feature = "parallel")]
+    return items.par_iter().map(f).collect();
+
+    #[cfg(not(feature = "parallel"))]
+    return items.iter().map(f).collect();
+}
+
+/// Map `f` over `items` and their indices, failing with the error for the
+/// first item that fails
+pub fn try_map<T: Sync, U: Send, E: Send>(
+    items: &[T],
+    f: impl Fn(usize, &T) -> Result<U, E> + Sync + Send,
+) -> Result<Vec<U>, E> {
+    // rayon would stop at whichever error it happens to see first, so
+    // every result is kept to report the same error as the serial path
+    #[cfg(feature = "parallel")]
+    return items
+        .par_iter()
+        .enumerate()
+        .map(|(idx, item)| f(idx, item))
+        .collect::<Vec<Result<U, E>>>()
+        .into_iter()
+        .collect();
+
+    #[cfg(not(feature = "parallel"))]
+    return items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| f(idx, item))
+        .collect();
+}
+
+/// Stable sort of `items` by `compare`
+pub fn sort_by<T: Send>(items: &mut [T], compare: impl Fn(&T, &T) -> Ordering + Sync) {
+    #[cfg(feature = "parallel")]
+    items.par_sort_by(compare);
+
+    #[cfg(not(feature = "parallel"))]
+    items.sort_by(compare);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_map_test() {
+        let numbers = ["1", "2", "x", "4", "y"];
+
+        assert_eq!(
+            try_map(&numbers[..2], |idx, number| number
+                .parse::<usize>()
+                .map(|number| number + idx)),
+            Ok(vec![1, 3])
+        );
+        assert_eq!(
+            try_map(&numbers, |idx, number| number
+                .parse::<u32>()
+                .map_err(|_| idx)),
+            Err(2)
+        );
+    }
+
+    #[test]
+    fn sort_by_test() {
+        let mut pairs: Vec<(u32, usize)> = (0..1000).map(|idx| (idx % 7, idx as usize)).collect();
+        sort_by(&mut pairs, |a, b| a.0.cmp(&b.0));
+
+        // equal keys keep their original order
+        assert!(pairs.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
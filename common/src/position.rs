@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+/// Location of a parse error in a puzzle input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in bytes from the start of the line
+    pub column: usize,
+}
+
+impl Position {
+    /// Position of `part` within `text`, where `part` is a subslice of
+    /// `text`. Lines are counted from the start of `text`
+    pub fn within(text: &str, part: &str) -> Self {
+        let offset = (part.as_ptr() as usize)
+            .saturating_sub(text.as_ptr() as usize)
+            .min(text.len());
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+
+        Position {
+            line: before.matches('\n').count() + 1,
+            column: offset - line_start + 1,
+        }
+    }
+
+    /// Position just past the end of `part` within `text`, for syntax
+    /// that is missing
+    pub fn end_of(text: &str, part: &str) -> Self {
+        Position::within(text, &part[part.len()..])
+    }
+
+    /// Move the position down by `lines` lines
+    pub fn on_line(self, lines: usize) -> Self {
+        Position {
+            line: self.line + lines,
+            ..self
+        }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn within_test() {
+        let line = "Game 12: 3 blue";
+        let (_, cubes) = line.split_once(": ").unwrap();
+
+        assert_eq!(
+            Position::within(line, cubes),
+            Position {
+                line: 1,
+                column: 10
+            }
+        );
+        assert_eq!(
+            Position::within(line, line),
+            Position { line: 1, column: 1 }
+        );
+        assert_eq!(Position::end_of(line, line).column, 16);
+    }
+
+    #[test]
+    fn within_multiple_lines_test() {
+        let text = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2";
+        let (_, mapping) = text.split_once("\n\n").unwrap();
+        let (_, ranges) = mapping.split_once('\n').unwrap();
+
+        assert_eq!(
+            Position::within(text, ranges),
+            Position { line: 4, column: 1 }
+        );
+        assert_eq!(
+            Position::end_of(text, text),
+            Position { line: 4, column: 8 }
+        );
+        assert_eq!(
+            Position::end_of(text, mapping),
+            Position { line: 4, column: 8 }
+        );
+    }
+
+    #[test]
+    fn on_line_test() {
+        let position = Position { line: 1, column: 4 }.on_line(2);
+        assert_eq!(position, Position { line: 3, column: 4 });
+        assert_eq!(position.to_string(), "3:4");
+    }
+}
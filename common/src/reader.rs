@@ -0,0 +1,98 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead},
+};
+
+/// Error from solving a puzzle input streamed out of a reader
+#[derive(Debug)]
+pub enum ReadError<E> {
+    /// The reader failed, or the input isn't UTF-8
+    Io(io::Error),
+    /// A line that doesn't match the puzzle format
+    Parse(E),
+}
+
+impl<E: Display> Display for ReadError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::Io(err) => write!(f, "failed to read input: {err}"),
+            ReadError::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for ReadError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadError::Io(err) => Some(err),
+            ReadError::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl<E> From<io::Error> for ReadError<E> {
+    fn from(err: io::Error) -> Self {
+        ReadError::Io(err)
+    }
+}
+
+/// Call `f` with the 0-based index and text of each line in `reader`.
+/// Lines end the same way as with `str::lines`, and one buffer is reused
+/// for every line so memory doesn't grow with the length of the input
+pub fn for_each_line<E>(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(usize, &str) -> Result<(), E>,
+) -> Result<(), ReadError<E>> {
+    let mut buffer = String::new();
+
+    for idx in 0.. {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            break;
+        }
+
+        let line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        f(idx, line).map_err(ReadError::Parse)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn for_each_line_test() {
+        let text = "one\r\ntwo\n\nfour\n";
+        let mut lines = vec![];
+
+        for_each_line(text.as_bytes(), |idx, line| {
+            lines.push((idx, line.to_string()));
+            Ok::<(), ()>(())
+        })
+        .unwrap();
+
+        assert_eq!(
+            lines,
+            text.lines()
+                .map(String::from)
+                .enumerate()
+                .collect::<Vec<_>>()
+        );
+        assert!(matches!(
+            for_each_line(&b"ok\n\xff"[..], |_, _| Ok::<(), ()>(())),
+            Err(ReadError::Io(_))
+        ));
+        assert!(matches!(
+            for_each_line("a\nb".as_bytes(), |idx, _| if idx == 1 {
+                Err(idx)
+            } else {
+                Ok(())
+            }),
+            Err(ReadError::Parse(1))
+        ));
+    }
+}
@@ -0,0 +1,67 @@
+use std::ops::Range;
+
+/// Small seeded pseudo-random number generator (SplitMix64) for generating
+/// puzzle inputs. The same seed always produces the same numbers, on any
+/// platform and across dependency updates
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Number in `range`, which must not be empty
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "empty range {range:?}");
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// Index below `len`, which must not be zero
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..len as u64) as usize
+    }
+
+    /// True with a chance of one in `n`
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.range(0..n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.index(idx + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers_test() {
+        let mut rng = Rng::new(7);
+        let mut other = Rng::new(7);
+
+        for _ in 0..100 {
+            let number = rng.range(10..20);
+            assert_eq!(number, other.range(10..20));
+            assert!((10..20).contains(&number));
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+}
@@ -0,0 +1,28 @@
+use crate::Answer;
+
+/// Named way of solving one part of a day from its input, for timing
+/// the algorithms a day could use against each other
+#[derive(Debug)]
+pub struct Variant<E> {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: fn(&str) -> Result<Answer, E>,
+    /// Why the variant would take too long on an input, when it would
+    pub too_slow: fn(&str) -> Option<String>,
+}
+
+impl<E> Variant<E> {
+    pub const fn new(name: &'static str, part: u8, solve: fn(&str) -> Result<Answer, E>) -> Self {
+        Variant {
+            name,
+            part,
+            solve,
+            too_slow: |_| None,
+        }
+    }
+
+    /// Variant that isn't run on inputs `too_slow` gives a reason for
+    pub const fn unless(self, too_slow: fn(&str) -> Option<String>) -> Self {
+        Variant { too_slow, ..self }
+    }
+}
@@ -0,0 +1,137 @@
+use std::borrow::Borrow;
+
+use num_integer::Roots;
+use num_traits::{CheckedAdd, CheckedMul, One, Zero};
+
+use crate::Answer;
+
+/// Unsigned integer for values with no natural bound, like products and
+/// counts that keep doubling. It's `u128`, or arbitrary precision with
+/// the `bigint` feature, where none of the checked operations overflow
+#[cfg(not(feature = "bigint"))]
+pub type Wide = u128;
+
+/// Arbitrary precision [`Wide`], so none of the checked operations
+/// overflow
+#[cfg(feature = "bigint")]
+pub type Wide = num_bigint::BigUint;
+
+#[cfg(not(feature = "bigint"))]
+impl From<&Wide> for Answer {
+    fn from(value: &Wide) -> Self {
+        Answer::Unsigned(*value)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl From<&Wide> for Answer {
+    fn from(value: &Wide) -> Self {
+        value.clone().into()
+    }
+}
+
+/// Sum of `values`, or the index of the value that made it overflow
+pub fn checked_sum<T: Borrow<Wide>>(values: impl IntoIterator<Item = T>) -> Result<Wide, usize> {
+    values
+        .into_iter()
+        .enumerate()
+        .try_fold(Wide::zero(), |sum, (idx, value)| {
+            CheckedAdd::checked_add(&sum, value.borrow()).ok_or(idx)
+        })
+}
+
+/// Product of `values`, or the index of the value that made it overflow
+pub fn checked_product<T: Borrow<Wide>>(
+    values: impl IntoIterator<Item = T>,
+) -> Result<Wide, usize> {
+    values
+        .into_iter()
+        .enumerate()
+        .try_fold(Wide::one(), |product, (idx, value)| {
+            CheckedMul::checked_mul(&product, value.borrow()).ok_or(idx)
+        })
+}
+
+/// `a + b`, `None` when it overflows
+pub fn checked_add(a: &Wide, b: &Wide) -> Option<Wide> {
+    CheckedAdd::checked_add(a, b)
+}
+
+/// `a * b`, `None` when it overflows
+pub fn checked_mul(a: &Wide, b: &Wide) -> Option<Wide> {
+    CheckedMul::checked_mul(a, b)
+}
+
+/// `base` to the power of `exp`, `None` when it overflows
+pub fn checked_pow(base: u32, exp: u32) -> Option<Wide> {
+    num_traits::checked_pow(Wide::from(base), usize::try_from(exp).ok()?)
+}
+
+/// Square root of `value`, rounded down
+pub fn sqrt(value: &Wide) -> Wide {
+    Roots::sqrt(value)
+}
+
+/// Serde for a [`Wide`] as a string of its decimal digits, for use as
+/// `#[serde(with = "common::wide_decimal")]`. JSON numbers can't hold
+/// every `Wide`, and a `BigUint` on its own serializes as a list of
+/// 32-bit digits
+#[cfg(feature = "serde")]
+pub mod decimal {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use super::Wide;
+
+    pub fn serialize<S: Serializer>(value: &Wide, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Wide, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_test() {
+        let wide = |values: [u32; 3]| values.map(Wide::from);
+
+        assert_eq!(checked_sum(wide([1, 2, 3])), Ok(Wide::from(6u32)));
+        assert_eq!(checked_product(&wide([2, 3, 4])[..]), Ok(Wide::from(24u32)));
+        assert_eq!(
+            checked_add(&Wide::from(2u32), &Wide::from(3u32)),
+            Some(Wide::from(5u32))
+        );
+        assert_eq!(checked_pow(2, 10), Some(Wide::from(1024u32)));
+        assert_eq!(sqrt(&Wide::from(99u32)), Wide::from(9u32));
+    }
+
+    #[test]
+    #[cfg(not(feature = "bigint"))]
+    fn overflow_test() {
+        assert_eq!(checked_sum([u128::MAX, 0, 1, 1]), Err(2));
+        assert_eq!(checked_add(&u128::MAX, &1), None);
+        assert_eq!(
+            checked_product([u64::MAX.into(), 1, u64::MAX.into(), 2]),
+            Err(3)
+        );
+        assert_eq!(checked_pow(2, 127), Some(1 << 127));
+        assert_eq!(checked_pow(2, 128), None);
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn bigint_test() {
+        let big = checked_pow(2, 200).unwrap();
+        let max = Wide::from(u128::MAX);
+
+        assert_eq!(checked_sum([&max, &Wide::from(1u32)]), Ok(&max + 1u32));
+        assert_eq!(checked_mul(&big, &big), checked_pow(2, 400));
+        assert_eq!(sqrt(&big), checked_pow(2, 100).unwrap());
+    }
+}
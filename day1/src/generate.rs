@@ -0,0 +1,51 @@
+use std::fmt::Write;
+
+use common::Rng;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Calibration document of `size` lines mixing letters, digits and
+/// spelled out digits. Every line has at least one digit so both parts
+/// can solve it
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let chunks = rng.range(1..8);
+        let digit_chunk = rng.range(0..chunks);
+
+        for chunk in 0..chunks {
+            if chunk == digit_chunk || rng.one_in(4) {
+                write!(input, "{}", rng.range(1..10)).unwrap();
+            } else if rng.one_in(2) {
+                let word = WORDS[rng.index(WORDS.len())];
+                input.push_str(word);
+            } else {
+                for _ in 0..rng.range(1..5) {
+                    input.push(char::from(b'a' + rng.range(0..26) as u8));
+                }
+            }
+        }
+
+        input.push('\n');
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let input = generate(&mut Rng::new(1), 100);
+
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        assert_eq!(input.lines().count(), 100);
+        assert!(crate::try_part1(&input).is_ok());
+        assert!(crate::try_part2(&input).is_ok());
+    }
+}
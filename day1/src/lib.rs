@@ -1,86 +1,358 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, io::BufRead, str::FromStr};
 
-struct CalibartionValue {
-    value: String,
+use common::{for_each_line, par, Answer, Explanation, Position, ReadError, Solution};
+
+mod generate;
+mod naive;
+#[cfg(test)]
+mod proptests;
+
+pub use generate::generate;
+pub use naive::{generate_small, naive_part1, naive_part2};
+
+/// Error from reading a calibration document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A line without any digit to take the calibration value from
+    MissingDigit { position: Position },
+}
+
+impl ParseError {
+    pub fn position(&self) -> Position {
+        match self {
+            ParseError::MissingDigit { position } => *position,
+        }
+    }
+
+    fn on_line(self, lines: usize) -> Self {
+        match self {
+            ParseError::MissingDigit { position } => ParseError::MissingDigit {
+                position: position.on_line(lines),
+            },
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingDigit { position } => write!(f, "{position}: expected a digit"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Calibration value recovered from a line of the document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u32", into = "u32")
+)]
+pub struct CalibartionValue {
+    value: u32,
+}
+
+impl TryFrom<u32> for CalibartionValue {
+    type Error = String;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if value > 99 {
+            return Err(format!("expected a two digit value, found {value}"));
+        }
+        Ok(CalibartionValue::new(value / 10, value % 10))
+    }
+}
+
+impl From<CalibartionValue> for u32 {
+    fn from(calibration_value: CalibartionValue) -> Self {
+        calibration_value.value
+    }
 }
 
 impl CalibartionValue {
-    fn new(first: &str, last: &str) -> Self {
+    /// Value made of the first and last digits found on a line
+    ///
+    /// # Panics
+    ///
+    /// If either digit is more than 9
+    pub fn new(first: u32, last: u32) -> Self {
+        assert!(first <= 9 && last <= 9, "digits are 0 to 9");
         CalibartionValue {
-            value: format!("{first}{last}"),
+            value: first * 10 + last,
         }
     }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
 }
 
 impl FromStr for CalibartionValue {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let nums: Vec<&str> = s.matches(char::is_numeric).collect();
-
-        match nums.len() {
-            0 => Err(()),
-            _ => Ok(CalibartionValue::new(
-                nums.first().unwrap(),
-                nums.last().unwrap(),
-            )),
+        let nums: Vec<u32> = s.chars().filter_map(|c| c.to_digit(10)).collect();
+
+        match (nums.first(), nums.last()) {
+            (Some(first), Some(last)) => Ok(CalibartionValue::new(*first, *last)),
+            _ => Err(ParseError::MissingDigit {
+                position: Position::within(s, s),
+            }),
         }
     }
 }
 
-pub fn part1(input: &str) -> i32 {
+/// Calibration value taken from one line of the document, as a step of
+/// an explanation
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineValue {
+    /// 1-based line number
+    pub line: usize,
+    pub text: String,
+    pub value: u32,
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(lines: &Self::Input) -> Result<Answer, Self::Error> {
+        let sum = par::try_map(lines, |idx, line| {
+            line.parse::<CalibartionValue>()
+                .map(|calibration_value| calibration_value.value)
+                .map_err(|err| err.on_line(idx))
+        })?
+        .into_iter()
+        .map(u64::from)
+        .sum::<u64>();
+
+        Ok(sum.into())
+    }
+
+    fn part2(lines: &Self::Input) -> Result<Option<Answer>, Self::Error> {
+        let sum = par::try_map(lines, |idx, line| {
+            replace_digit_words(line)
+                .parse::<CalibartionValue>()
+                .map(|calibration_value| calibration_value.value)
+                .map_err(|err| err.on_line(idx))
+        })?
+        .into_iter()
+        .map(u64::from)
+        .sum::<u64>();
+
+        Ok(Some(sum.into()))
+    }
+}
+
+// leave first and last character of digit words
+// to solve the issue with overlaps
+const DIGIT_WORDS: [(&str, &str); 9] = [
+    ("one", "o1e"),
+    ("two", "t2o"),
+    ("three", "t3e"),
+    ("four", "f4r"),
+    ("five", "f5e"),
+    ("six", "s6x"),
+    ("seven", "s7n"),
+    ("eight", "e8t"),
+    ("nine", "n9e"),
+];
+
+/// Write the digit into every spelled out digit in `line`
+fn replace_digit_words(line: &str) -> String {
+    let mut new_line = line.to_string();
+    for (word, digit) in DIGIT_WORDS {
+        new_line = new_line.replace(word, digit);
+    }
+
+    new_line
+}
+
+/// Sum the calibration values of each line in `reader`, one line at a
+/// time, with `words` choosing whether spelled out digits count
+fn sum_reader(reader: impl BufRead, words: bool) -> Result<Answer, ReadError<ParseError>> {
+    let mut sum: u64 = 0;
+
+    for_each_line(reader, |idx, line| {
+        let calibration_value = if words {
+            replace_digit_words(line).parse::<CalibartionValue>()
+        } else {
+            line.parse::<CalibartionValue>()
+        }
+        .map_err(|err| err.on_line(idx))?;
+
+        sum += u64::from(calibration_value.value);
+        Ok(())
+    })?;
+
+    Ok(sum.into())
+}
+
+pub fn try_part1(input: &str) -> Result<Answer, ParseError> {
+    Day1::part1(&Day1::parse(input)?)
+}
+
+pub fn try_part2(input: &str) -> Result<Answer, ParseError> {
+    let answer = Day1::part2(&Day1::parse(input)?)?;
+    Ok(answer.expect("part 2 is solved"))
+}
+
+pub fn part1(input: &str) -> Answer {
+    try_part1(input).unwrap()
+}
+
+pub fn part2(input: &str) -> Answer {
+    try_part2(input).unwrap()
+}
+
+/// Every line without a calibration value for either part, where
+/// solving stops at the first
+pub fn lint(input: &str) -> Vec<ParseError> {
     input
         .lines()
-        .map(|line| line.parse::<CalibartionValue>().unwrap().value)
-        .map(|value| value.parse::<i32>().unwrap())
-        .sum()
-}
-
-pub fn part2(input: &str) -> i32 {
-    // leave first and last character of digit words
-    // to solve the issue with overlaps
-    let array = [
-        ("one", "o1e"),
-        ("two", "t2o"),
-        ("three", "t3e"),
-        ("four", "f4r"),
-        ("five", "f5e"),
-        ("six", "s6x"),
-        ("seven", "s7n"),
-        ("eight", "e8t"),
-        ("nine", "n9e"),
-    ];
+        .enumerate()
+        // spelling out digits never removes one, so a line with a value for
+        // part 1 has one for part 2 as well
+        .filter_map(|(idx, line)| {
+            line.parse::<CalibartionValue>()
+                .err()
+                .map(|err| err.on_line(idx))
+        })
+        .collect()
+}
 
-    input
+/// Part 1 along with the value taken from every line
+pub fn explain_part1(input: &str) -> Result<Explanation<LineValue>, ParseError> {
+    explain(input, false)
+}
+
+/// Part 2 along with the value taken from every line, once spelled out
+/// digits are counted
+pub fn explain_part2(input: &str) -> Result<Explanation<LineValue>, ParseError> {
+    explain(input, true)
+}
+
+fn explain(input: &str, words: bool) -> Result<Explanation<LineValue>, ParseError> {
+    let steps = input
         .lines()
-        .map(|line| {
-            let mut new_line = String::from(line);
-            for x in array {
-                new_line = new_line.replace(x.0, x.1);
+        .enumerate()
+        .map(|(idx, line)| {
+            let calibration_value = if words {
+                replace_digit_words(line).parse::<CalibartionValue>()
+            } else {
+                line.parse::<CalibartionValue>()
             }
+            .map_err(|err| err.on_line(idx))?;
 
-            new_line
+            Ok(LineValue {
+                line: idx + 1,
+                text: line.to_string(),
+                value: calibration_value.value,
+            })
         })
-        .map(|line| line.parse::<CalibartionValue>().unwrap().value)
-        .map(|value| value.parse::<i32>().unwrap())
-        .sum()
+        .collect::<Result<Vec<_>, ParseError>>()?;
+
+    let sum: u64 = steps.iter().map(|step| u64::from(step.value)).sum();
+    Ok(Explanation::new(sum, steps))
+}
+
+/// Part 1 over a document streamed line by line, for inputs too large to
+/// read into memory
+pub fn part1_reader(reader: impl BufRead) -> Result<Answer, ReadError<ParseError>> {
+    sum_reader(reader, false)
+}
+
+/// Part 2 over a document streamed line by line
+pub fn part2_reader(reader: impl BufRead) -> Result<Answer, ReadError<ParseError>> {
+    sum_reader(reader, true)
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = include_str!("../input.txt");
+    const INPUT: &str = include_str!("../input.txt");
+
+    #[test]
+    fn lint_test() {
+        let errors: Vec<String> = lint("1abc2\npqrstuvwxyz\nthree\nxyz")
+            .iter()
+            .map(ParseError::to_string)
+            .collect();
+
+        // `three` has a value for part 2 but not part 1
+        assert_eq!(
+            errors,
+            [
+                "2:1: expected a digit",
+                "3:1: expected a digit",
+                "4:1: expected a digit"
+            ]
+        );
+        assert!(try_part1("three").is_err());
+        assert_eq!(lint(include_str!("../examples/example1.txt")), []);
+    }
+
+    #[test]
+    fn explain_test() {
+        let explanation = explain_part2("two1nine\n4nineeightseven2").unwrap();
+
+        assert_eq!(explanation.answer, Answer::from(71));
+        assert_eq!(
+            explanation.steps[1],
+            LineValue {
+                line: 2,
+                text: "4nineeightseven2".to_string(),
+                value: 42,
+            }
+        );
+        assert_eq!(explain_part1(INPUT).unwrap().answer, part1(INPUT));
+        assert_eq!(explain_part2(INPUT).unwrap().answer, part2(INPUT));
+    }
 
     #[test]
-    fn part1_test() {
-        let result = part1(INPUT);
-        assert_eq!(result, 54877);
+    fn missing_digit_test() {
+        let result = try_part1("1abc2\npqrstuvwxyz");
+        assert_eq!(
+            result,
+            Err(ParseError::MissingDigit {
+                position: Position { line: 2, column: 1 }
+            })
+        );
+        assert_eq!(
+            try_part2("1abc2\npqrstuvwxyz\ntwo")
+                .unwrap_err()
+                .position()
+                .line,
+            2
+        );
     }
 
     #[test]
-    fn part2_test() {
-        let result = part2(INPUT);
-        assert_eq!(result, 54100);
+    fn reader_test() {
+        assert_eq!(part1_reader(INPUT.as_bytes()).unwrap(), part1(INPUT));
+        assert_eq!(part2_reader(INPUT.as_bytes()).unwrap(), part2(INPUT));
+        assert!(matches!(
+            part1_reader("1abc2\r\npqrstuvwxyz\r\n".as_bytes()),
+            Err(ReadError::Parse(ParseError::MissingDigit {
+                position: Position { line: 2, column: 1 }
+            }))
+        ));
     }
 }
@@ -0,0 +1,62 @@
+use common::{Answer, Position, Rng};
+
+use crate::{generate, ParseError};
+
+pub(crate) const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Calibration value found by checking every position of the line for a
+/// digit or, with `words`, a spelled out digit
+pub(crate) fn naive_value(line: &str, words: bool) -> Option<u32> {
+    let digits: Vec<u32> = (0..line.len())
+        .filter_map(|idx| {
+            let rest = line.get(idx..)?;
+            let digit = rest.chars().next()?.to_digit(10);
+            let word = || {
+                WORDS
+                    .iter()
+                    .position(|word| rest.starts_with(word))
+                    .map(|digit| digit as u32 + 1)
+            };
+
+            digit.or_else(|| words.then(word).flatten())
+        })
+        .collect();
+
+    Some(digits.first()? * 10 + digits.last()?)
+}
+
+fn naive_sum(input: &str, words: bool) -> Result<Answer, ParseError> {
+    let mut sum: u64 = 0;
+
+    for (idx, line) in input.lines().enumerate() {
+        let value = naive_value(line, words).ok_or(ParseError::MissingDigit {
+            position: Position {
+                line: idx + 1,
+                column: 1,
+            },
+        })?;
+        sum += u64::from(value);
+    }
+
+    Ok(sum.into())
+}
+
+/// Part 1 by checking every position of every line for a digit, as a
+/// reference for [`crate::try_part1`]
+pub fn naive_part1(input: &str) -> Result<Answer, ParseError> {
+    naive_sum(input, false)
+}
+
+/// Part 2 by checking every position of every line for a digit or a
+/// spelled out digit, as a reference for [`crate::try_part2`]
+pub fn naive_part2(input: &str) -> Result<Answer, ParseError> {
+    naive_sum(input, true)
+}
+
+/// Input small enough for the naive solvers
+pub fn generate_small(rng: &mut Rng) -> String {
+    let size = 1 + rng.index(8);
+    generate(rng, size)
+}
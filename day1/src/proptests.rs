@@ -0,0 +1,42 @@
+use proptest::prelude::*;
+
+use super::*;
+use crate::naive::{naive_value, WORDS};
+
+fn line_with_words() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![
+            prop::sample::select(&WORDS[..]).prop_map(String::from),
+            "[a-z0-9]",
+        ],
+        1..10,
+    )
+    .prop_map(|chunks| chunks.concat())
+}
+
+proptest! {
+    #[test]
+    fn parse_never_panics(input in "\\PC*") {
+        let _ = Day1::parse(&input);
+    }
+
+    #[test]
+    fn part1_matches_naive_scan(lines in prop::collection::vec("[a-z0-9]{1,12}", 1..20)) {
+        let expected = lines
+            .iter()
+            .map(|line| naive_value(line, false))
+            .sum::<Option<u32>>();
+
+        prop_assert_eq!(try_part1(&lines.join("\n")).ok(), expected.map(Answer::from));
+    }
+
+    #[test]
+    fn part2_matches_naive_scan(lines in prop::collection::vec(line_with_words(), 1..20)) {
+        let expected = lines
+            .iter()
+            .map(|line| naive_value(line, true))
+            .sum::<Option<u32>>();
+
+        prop_assert_eq!(try_part2(&lines.join("\n")).ok(), expected.map(Answer::from));
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt::Write;
+
+use common::Rng;
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// Record of `size` games, each revealing one to six sets of cubes
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for id in 1..=size {
+        let cube_sets = (0..rng.range(1..7))
+            .map(|_| {
+                let mut colours = COLOURS;
+                rng.shuffle(&mut colours);
+                let count = rng.range(1..4) as usize;
+
+                colours[..count]
+                    .iter()
+                    .map(|colour| format!("{} {colour}", rng.range(1..21)))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .collect::<Vec<String>>()
+            .join("; ");
+
+        writeln!(input, "Game {id}: {cube_sets}").unwrap();
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let input = generate(&mut Rng::new(1), 100);
+
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        assert_eq!(input.lines().count(), 100);
+        assert!(crate::try_part1(&input).is_ok());
+        assert!(crate::try_part2(&input).is_ok());
+    }
+}
@@ -1,118 +1,667 @@
-use std::{cmp, str::FromStr};
+use std::{borrow::Cow, error::Error, fmt::Display, io::BufRead, str::FromStr};
 
-#[derive(Default)]
-struct CubeSet {
-    green: u32,
-    blue: u32,
-    red: u32,
+use common::{
+    checked_add, checked_sum, for_each_line, par, Answer, Explanation, Position, ReadError,
+    Solution, Wide,
+};
+
+mod generate;
+#[cfg(test)]
+mod proptests;
+
+pub use generate::generate;
+
+/// Error from reading the record of games
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Syntax that a game line is missing
+    Expected {
+        position: Position,
+        expected: &'static str,
+    },
+    /// A game id or cube count that isn't a number
+    InvalidNumber { position: Position, text: String },
+    /// A cube colour other than red, green or blue
+    UnknownColour { position: Position, colour: String },
+    /// A sum too large for [`Wide`] without the `bigint` feature
+    Overflow { position: Position },
+}
+
+impl ParseError {
+    pub fn position(&self) -> Position {
+        match self {
+            ParseError::Expected { position, .. }
+            | ParseError::InvalidNumber { position, .. }
+            | ParseError::UnknownColour { position, .. }
+            | ParseError::Overflow { position } => *position,
+        }
+    }
+
+    fn on_line(mut self, lines: usize) -> Self {
+        match &mut self {
+            ParseError::Expected { position, .. }
+            | ParseError::InvalidNumber { position, .. }
+            | ParseError::UnknownColour { position, .. }
+            | ParseError::Overflow { position } => *position = position.on_line(lines),
+        }
+        self
+    }
 }
 
-struct Game {
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Expected { position, expected } => {
+                write!(f, "{position}: expected {expected}")
+            }
+            ParseError::InvalidNumber { position, text } => {
+                write!(f, "{position}: expected a number, found `{text}`")
+            }
+            ParseError::UnknownColour { position, colour } => {
+                write!(
+                    f,
+                    "{position}: expected red, green or blue, found `{colour}`"
+                )
+            }
+            ParseError::Overflow { position } => {
+                write!(
+                    f,
+                    "{position}: too large to solve without the `bigint` feature"
+                )
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Overflow summing the game at `idx` in the record
+fn overflow(idx: usize) -> ParseError {
+    ParseError::Overflow {
+        position: Position {
+            line: idx + 1,
+            column: 1,
+        },
+    }
+}
+
+/// Colour of a cube in the bag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Colour {
+    Red,
+    Green,
+    Blue,
+}
+
+impl Display for Colour {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let colour = match self {
+            Colour::Red => "red",
+            Colour::Green => "green",
+            Colour::Blue => "blue",
+        };
+        write!(f, "{colour}")
+    }
+}
+
+/// Cubes shown at once from the bag, as the count of each colour in the
+/// order the record lists them
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Vec<(u32, Colour)>", into = "Vec<(u32, Colour)>")
+)]
+pub struct CubeSet {
+    cubes: Cow<'static, [(u32, Colour)]>,
+}
+
+/// Bag part 1 asks about: 12 red, 13 green and 14 blue cubes
+pub const BAG: CubeSet = CubeSet {
+    cubes: Cow::Borrowed(&[(12, Colour::Red), (13, Colour::Green), (14, Colour::Blue)]),
+};
+
+impl CubeSet {
+    /// Set of `red`, `green` and `blue` cubes, listed in that order
+    pub fn new(red: u32, green: u32, blue: u32) -> Self {
+        CubeSet::from_cubes(vec![
+            (red, Colour::Red),
+            (green, Colour::Green),
+            (blue, Colour::Blue),
+        ])
+    }
+
+    /// Set listing each count and colour of `cubes` in order
+    ///
+    /// # Panics
+    ///
+    /// If there are no cubes
+    pub fn from_cubes(cubes: Vec<(u32, Colour)>) -> Self {
+        CubeSet::try_from(cubes).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Count and colour of each cube shown, in the order they're listed
+    pub fn cubes(&self) -> &[(u32, Colour)] {
+        &self.cubes
+    }
+
+    /// Cubes of `colour` shown, the last count when it's listed twice
+    fn count(&self, colour: Colour) -> u32 {
+        self.cubes
+            .iter()
+            .rev()
+            .find(|(_, listed)| *listed == colour)
+            .map_or(0, |&(count, _)| count)
+    }
+
+    pub fn red(&self) -> u32 {
+        self.count(Colour::Red)
+    }
+
+    pub fn green(&self) -> u32 {
+        self.count(Colour::Green)
+    }
+
+    pub fn blue(&self) -> u32 {
+        self.count(Colour::Blue)
+    }
+
+    /// Red, green and blue cube counts multiplied together
+    pub fn power(&self) -> u128 {
+        u128::from(self.red()) * u128::from(self.green()) * u128::from(self.blue())
+    }
+}
+
+impl TryFrom<Vec<(u32, Colour)>> for CubeSet {
+    type Error = String;
+
+    fn try_from(cubes: Vec<(u32, Colour)>) -> Result<Self, Self::Error> {
+        if cubes.is_empty() {
+            return Err("expected at least one cube count and colour".to_string());
+        }
+
+        Ok(CubeSet {
+            cubes: Cow::Owned(cubes),
+        })
+    }
+}
+
+impl From<CubeSet> for Vec<(u32, Colour)> {
+    fn from(cube_set: CubeSet) -> Self {
+        cube_set.cubes.into_owned()
+    }
+}
+
+/// A game's id and every cube set shown during it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Game {
     id: u32,
     cube_sets: Vec<CubeSet>,
 }
 
 impl Game {
-    fn game_is_possible(&self) -> bool {
+    pub fn new(id: u32, cube_sets: Vec<CubeSet>) -> Self {
+        Game { id, cube_sets }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Cube sets in the order they were shown
+    pub fn cube_sets(&self) -> &[CubeSet] {
+        &self.cube_sets
+    }
+
+    /// Whether every cube set could have been shown from `bag`
+    pub fn is_possible(&self, bag: &CubeSet) -> bool {
         for cube_set in &self.cube_sets {
-            if cube_set.red > 12 || cube_set.green > 13 || cube_set.blue > 14 {
+            if cube_set.red() > bag.red()
+                || cube_set.green() > bag.green()
+                || cube_set.blue() > bag.blue()
+            {
                 return false;
             }
         }
         true
     }
 
-    fn min_cubes_required(&self) -> CubeSet {
-        let mut cube_set = CubeSet::default();
-
-        for set in &self.cube_sets {
-            cube_set.blue = cmp::max(cube_set.blue, set.blue);
-            cube_set.green = cmp::max(cube_set.green, set.green);
-            cube_set.red = cmp::max(cube_set.red, set.red);
-        }
+    /// Fewest cubes of each colour the bag could have held for this game
+    pub fn min_cubes_required(&self) -> CubeSet {
+        let most = |count: fn(&CubeSet) -> u32| self.cube_sets.iter().map(count).max().unwrap_or(0);
 
-        cube_set
+        CubeSet::new(
+            most(CubeSet::red),
+            most(CubeSet::green),
+            most(CubeSet::blue),
+        )
     }
 }
 
 impl FromStr for Game {
-    type Err = ();
+    type Err = ParseError;
 
     /// Create a game from a game string line
     /// Example: "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (game_id, cube_sets) = s.split_once(':').unwrap();
-        let (_, game_id) = game_id.split_once(' ').unwrap();
-        let id = game_id.parse::<u32>().unwrap();
+        let (game_id, cube_sets) = s.split_once(':').ok_or_else(|| ParseError::Expected {
+            position: Position::end_of(s, s),
+            expected: "':' after the game id",
+        })?;
+        let (_, game_id) = game_id
+            .split_once(' ')
+            .ok_or_else(|| ParseError::Expected {
+                position: Position::within(s, game_id),
+                expected: "a game id",
+            })?;
+        let id = parse_number(s, game_id)?;
 
-        let cube_sets: Vec<CubeSet> = cube_sets
+        let cube_sets = cube_sets
             .split(';')
-            .map(|cube_set| cube_set.parse::<CubeSet>().unwrap())
-            .collect();
+            .map(|cube_set| CubeSet::parse_within(s, cube_set))
+            .collect::<Result<Vec<CubeSet>, ParseError>>()?;
 
         Ok(Game { id, cube_sets })
     }
 }
 
+/// Canonical text of a game, a line of the record with its cube sets
+/// written as by [`CubeSet`]'s `Display`
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+
+        let mut separator = " ";
+        for cube_set in &self.cube_sets {
+            write!(f, "{separator}{cube_set}")?;
+            separator = "; ";
+        }
+
+        Ok(())
+    }
+}
+
+impl CubeSet {
+    /// Parse a cube set that is part of `line`, so errors point into
+    /// the whole line
+    fn parse_within(line: &str, s: &str) -> Result<Self, ParseError> {
+        let mut cubes = vec![];
+
+        for cube in s.split(',') {
+            let cube = cube.trim();
+            let (count, colour) = cube.split_once(' ').ok_or_else(|| ParseError::Expected {
+                position: Position::within(line, cube),
+                expected: "a cube count and colour",
+            })?;
+            let count = parse_number(line, count)?;
+            let colour = match colour {
+                "red" => Colour::Red,
+                "green" => Colour::Green,
+                "blue" => Colour::Blue,
+                _ => {
+                    return Err(ParseError::UnknownColour {
+                        position: Position::within(line, colour),
+                        colour: colour.to_string(),
+                    })
+                }
+            };
+            cubes.push((count, colour));
+        }
+
+        Ok(CubeSet {
+            cubes: Cow::Owned(cubes),
+        })
+    }
+}
+
+/// Canonical text of a cube set: each count and colour in the order
+/// they're listed, like `3 blue, 4 red`
+impl Display for CubeSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut separator = "";
+
+        for (count, colour) in self.cubes.iter() {
+            write!(f, "{separator}{count} {colour}")?;
+            separator = ", ";
+        }
+
+        Ok(())
+    }
+}
+
 impl FromStr for CubeSet {
-    type Err = ();
+    type Err = ParseError;
 
     /// Create Cubes set from a cube set string line
     /// Example: "1 red, 2 green, 6 blue"
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut cube_set = CubeSet::default();
+        CubeSet::parse_within(s, s)
+    }
+}
 
-        for cube in s.split(',') {
-            let cube = cube.trim();
-            let (count, colour) = cube.split_once(' ').unwrap();
-            let count = count.parse::<u32>().unwrap();
-            match colour {
-                "red" => cube_set.red = count,
-                "blue" => cube_set.blue = count,
-                "green" => cube_set.green = count,
-                _ => (),
-            }
-        }
+fn parse_number(line: &str, number: &str) -> Result<u32, ParseError> {
+    number
+        .parse::<u32>()
+        .map_err(|_| ParseError::InvalidNumber {
+            position: Position::within(line, number),
+            text: number.to_string(),
+        })
+}
+
+/// Most cubes of each colour a game showed and whether the bag could
+/// have held them, as a step of explaining part 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameCheck {
+    pub game: u32,
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+    pub possible: bool,
+}
+
+/// Fewest cubes of each colour a game needed and their power, as a step
+/// of explaining part 2
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamePower {
+    pub game: u32,
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+    pub power: u128,
+}
+
+pub struct Day2;
 
-        Ok(cube_set)
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Game>;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        let lines: Vec<&str> = input.lines().collect();
+        par::try_map(&lines, |idx, line| {
+            line.parse::<Game>().map_err(|err| err.on_line(idx))
+        })
+    }
+
+    fn part1(games: &Self::Input) -> Result<Answer, Self::Error> {
+        let ids = games.iter().map(|game| {
+            let id = if game.is_possible(&BAG) { game.id } else { 0 };
+            Wide::from(id)
+        });
+
+        Ok(checked_sum(ids).map_err(overflow)?.into())
+    }
+
+    fn part2(games: &Self::Input) -> Result<Option<Answer>, Self::Error> {
+        let powers = games
+            .iter()
+            .map(|game| Wide::from(game.min_cubes_required().power()));
+
+        Ok(Some(checked_sum(powers).map_err(overflow)?.into()))
     }
 }
 
-pub fn part1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| line.parse::<Game>().unwrap())
-        .filter(|game| game.game_is_possible())
-        .map(|game| game.id)
-        .sum()
+/// Sum `value` over each game in `reader`, parsing one line at a time
+fn sum_reader(
+    reader: impl BufRead,
+    value: impl Fn(&Game) -> Wide,
+) -> Result<Answer, ReadError<ParseError>> {
+    let mut sum = Wide::from(0u32);
+
+    for_each_line(reader, |idx, line| {
+        let game = line.parse::<Game>().map_err(|err| err.on_line(idx))?;
+        sum = checked_add(&sum, &value(&game)).ok_or_else(|| overflow(idx))?;
+        Ok(())
+    })?;
+
+    Ok(sum.into())
+}
+
+pub fn try_part1(input: &str) -> Result<Answer, ParseError> {
+    Day2::part1(&Day2::parse(input)?)
+}
+
+pub fn try_part2(input: &str) -> Result<Answer, ParseError> {
+    let answer = Day2::part2(&Day2::parse(input)?)?;
+    Ok(answer.expect("part 2 is solved"))
+}
+
+pub fn part1(input: &str) -> Answer {
+    try_part1(input).unwrap()
+}
+
+pub fn part2(input: &str) -> Answer {
+    try_part2(input).unwrap()
 }
 
-pub fn part2(input: &str) -> u32 {
+/// Every game line that doesn't parse, where parsing stops at the first
+pub fn lint(input: &str) -> Vec<ParseError> {
     input
         .lines()
-        .map(|line| line.parse::<Game>().unwrap())
+        .enumerate()
+        .filter_map(|(idx, line)| line.parse::<Game>().err().map(|err| err.on_line(idx)))
+        .collect()
+}
+
+/// Part 1 along with whether each game was possible
+pub fn explain_part1(input: &str) -> Result<Explanation<GameCheck>, ParseError> {
+    let steps: Vec<GameCheck> = Day2::parse(input)?
+        .iter()
+        .map(|game| {
+            let most = game.min_cubes_required();
+            GameCheck {
+                game: game.id,
+                red: most.red(),
+                green: most.green(),
+                blue: most.blue(),
+                possible: game.is_possible(&BAG),
+            }
+        })
+        .collect();
+
+    let ids = steps.iter().map(|step| {
+        let id = if step.possible { step.game } else { 0 };
+        Wide::from(id)
+    });
+    Ok(Explanation::new(checked_sum(ids).map_err(overflow)?, steps))
+}
+
+/// Part 2 along with the power of each game
+pub fn explain_part2(input: &str) -> Result<Explanation<GamePower>, ParseError> {
+    let steps: Vec<GamePower> = Day2::parse(input)?
+        .iter()
         .map(|game| {
-            let min_cube_set = game.min_cubes_required();
-            min_cube_set.blue * min_cube_set.green * min_cube_set.red
+            let fewest = game.min_cubes_required();
+            GamePower {
+                game: game.id,
+                red: fewest.red(),
+                green: fewest.green(),
+                blue: fewest.blue(),
+                power: fewest.power(),
+            }
         })
-        .sum()
+        .collect();
+
+    let powers = steps.iter().map(|step| Wide::from(step.power));
+    Ok(Explanation::new(
+        checked_sum(powers).map_err(overflow)?,
+        steps,
+    ))
+}
+
+/// Part 1 over a record streamed line by line, for inputs too large to
+/// read into memory
+pub fn part1_reader(reader: impl BufRead) -> Result<Answer, ReadError<ParseError>> {
+    sum_reader(reader, |game| {
+        let id = if game.is_possible(&BAG) { game.id } else { 0 };
+        Wide::from(id)
+    })
+}
+
+/// Part 2 over a record streamed line by line
+pub fn part2_reader(reader: impl BufRead) -> Result<Answer, ReadError<ParseError>> {
+    sum_reader(reader, |game| Wide::from(game.min_cubes_required().power()))
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = include_str!("../input.txt");
+    #[test]
+    fn display_test() {
+        let input = include_str!("../examples/example.txt");
+        for line in input
+            .lines()
+            .chain(["Game 1: 3 blue, 4 red; 2 green", "Game 2: 0 green, 1 red"])
+        {
+            assert_eq!(line.parse::<Game>().unwrap().to_string(), line);
+        }
+
+        let empty = Game::new(1, vec![CubeSet::new(0, 0, 0)]);
+        assert_eq!(empty.to_string(), "Game 1: 0 red, 0 green, 0 blue");
+        assert_eq!(empty.to_string().parse::<Game>().unwrap(), empty);
+    }
+
+    #[test]
+    fn model_test() {
+        let game = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green"
+            .parse::<Game>()
+            .unwrap();
+
+        assert_eq!(
+            game,
+            Game::new(
+                3,
+                vec![
+                    CubeSet::from_cubes(vec![
+                        (8, Colour::Green),
+                        (6, Colour::Blue),
+                        (20, Colour::Red)
+                    ]),
+                    CubeSet::from_cubes(vec![
+                        (5, Colour::Blue),
+                        (4, Colour::Red),
+                        (13, Colour::Green)
+                    ]),
+                ]
+            )
+        );
+        assert_eq!(game.id(), 3);
+        assert_eq!(game.cube_sets()[1].green(), 13);
+        assert_eq!(game.cube_sets()[1].cubes()[0], (5, Colour::Blue));
+        assert!(!game.is_possible(&BAG));
+        assert_eq!(game.min_cubes_required(), CubeSet::new(20, 13, 6));
+        assert_eq!(game.min_cubes_required().power(), 1560);
+    }
+
+    #[test]
+    fn large_power_test() {
+        let input = "Game 1: 4294967295 red, 4294967295 green, 4294967295 blue\n\
+                     Game 2: 4294967295 red, 4294967295 green, 4294967295 blue";
+        let power = u128::from(u32::MAX).pow(3);
+
+        assert_eq!(try_part2(input).unwrap(), power * 2);
+        assert_eq!(part2_reader(input.as_bytes()).unwrap(), power * 2);
+    }
+
+    #[test]
+    fn lint_test() {
+        let input = "Game 1: 3 blue\nGame 2: 1 blue, 2 purple\nGame 3: x blue";
+        let errors: Vec<String> = lint(input).iter().map(ParseError::to_string).collect();
+
+        assert_eq!(
+            errors,
+            [
+                "2:19: expected red, green or blue, found `purple`",
+                "3:9: expected a number, found `x`",
+            ]
+        );
+        assert_eq!(lint(include_str!("../examples/example.txt")), []);
+    }
+
+    #[test]
+    fn explain_test() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green\nGame 3: 8 green, 6 blue, 20 red";
+        let explanation = explain_part1(input).unwrap();
+
+        assert_eq!(explanation.answer, Answer::from(1));
+        assert_eq!(
+            explanation.steps,
+            [
+                GameCheck {
+                    game: 1,
+                    red: 4,
+                    green: 2,
+                    blue: 3,
+                    possible: true,
+                },
+                GameCheck {
+                    game: 3,
+                    red: 20,
+                    green: 8,
+                    blue: 6,
+                    possible: false,
+                },
+            ]
+        );
+        assert_eq!(explain_part2(input).unwrap().steps[1].power, 960);
+    }
 
     #[test]
-    fn part1_test() {
-        let result = part1(INPUT);
-        assert_eq!(result, 8);
+    fn parse_error_test() {
+        let unknown_colour = "Game 1: 3 blue\nGame 2: 1 blue, 2 purple";
+        let invalid_count = "Game 1: x blue";
+        let missing_colon = "Game 1 3 blue";
+
+        assert_eq!(
+            try_part1(unknown_colour),
+            Err(ParseError::UnknownColour {
+                position: Position {
+                    line: 2,
+                    column: 19
+                },
+                colour: "purple".to_string(),
+            })
+        );
+        assert_eq!(
+            try_part1(invalid_count),
+            Err(ParseError::InvalidNumber {
+                position: Position { line: 1, column: 9 },
+                text: "x".to_string(),
+            })
+        );
+        assert_eq!(
+            try_part2(missing_colon).unwrap_err().to_string(),
+            "1:14: expected ':' after the game id"
+        );
     }
 
     #[test]
-    fn part2_test() {
-        let result = part2(INPUT);
-        assert_eq!(result, 2286);
+    fn reader_test() {
+        let input = include_str!("../examples/example.txt");
+
+        assert_eq!(part1_reader(input.as_bytes()).unwrap(), part1(input));
+        assert_eq!(part2_reader(input.as_bytes()).unwrap(), part2(input));
+        assert_eq!(
+            part1_reader("Game 1: 3 blue\nGame 2: 1 blue, 2 purple".as_bytes())
+                .unwrap_err()
+                .to_string(),
+            "2:19: expected red, green or blue, found `purple`"
+        );
     }
 }
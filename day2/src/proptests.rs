@@ -0,0 +1,67 @@
+use proptest::prelude::*;
+
+use super::*;
+
+/// Red, green and blue counts of a cube set with at least one cube
+fn cube_set() -> impl Strategy<Value = (u32, u32, u32)> {
+    (0..30u32, 0..30u32, 0..30u32).prop_filter(
+        "a cube set reveals at least one cube",
+        |&(red, green, blue)| red + green + blue > 0,
+    )
+}
+
+/// Game line listing the colours of each set in red, green, blue order
+/// and leaving out colours that weren't revealed
+fn format_game(id: u32, cube_sets: &[(u32, u32, u32)]) -> String {
+    let cube_sets = cube_sets
+        .iter()
+        .map(|&(red, green, blue)| {
+            [(red, "red"), (green, "green"), (blue, "blue")]
+                .iter()
+                .filter(|(count, _)| *count > 0)
+                .map(|(count, colour)| format!("{count} {colour}"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        })
+        .collect::<Vec<String>>()
+        .join("; ");
+
+    format!("Game {id}: {cube_sets}")
+}
+
+fn cube_counts(game: &Game) -> Vec<(u32, u32, u32)> {
+    game.cube_sets
+        .iter()
+        .map(|cube_set| (cube_set.red(), cube_set.green(), cube_set.blue()))
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn parse_never_panics(input in "\\PC*") {
+        let _ = Day2::parse(&input);
+    }
+
+    #[test]
+    fn game_round_trip(id in 1..1000u32, cube_sets in prop::collection::vec(cube_set(), 1..6)) {
+        let line = format_game(id, &cube_sets);
+        let game = line.parse::<Game>().unwrap();
+
+        prop_assert_eq!(game.to_string(), line.clone());
+        prop_assert_eq!(format_game(game.id, &cube_counts(&game)), line);
+    }
+
+    #[test]
+    fn min_cubes_cover_every_set(cube_sets in prop::collection::vec(cube_set(), 1..6)) {
+        let game = format_game(1, &cube_sets).parse::<Game>().unwrap();
+        let min_cube_set = game.min_cubes_required();
+
+        prop_assert_eq!(min_cube_set.red(), cube_sets.iter().map(|set| set.0).max().unwrap());
+        prop_assert_eq!(min_cube_set.green(), cube_sets.iter().map(|set| set.1).max().unwrap());
+        prop_assert_eq!(min_cube_set.blue(), cube_sets.iter().map(|set| set.2).max().unwrap());
+        prop_assert_eq!(
+            game.is_possible(&BAG),
+            min_cube_set.red() <= 12 && min_cube_set.green() <= 13 && min_cube_set.blue() <= 14
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use common::Rng;
+
+use crate::SYMBOLS;
+
+/// Square schematic `size` cells wide with numbers of up to three digits
+/// scattered between symbols and empty cells. Numbers are always
+/// followed by a `.` so they never run into each other
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let symbols: Vec<char> = SYMBOLS.chars().collect();
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let mut row = String::new();
+
+        while row.len() < size {
+            match rng.range(0..10) {
+                0 | 1 => {
+                    row.push_str(&rng.range(1..1000).to_string());
+                    row.push('.');
+                }
+                // gears show up as often as every other symbol together
+                2 => row.push('*'),
+                3 => row.push(*rng.pick(&symbols)),
+                _ => row.push('.'),
+            }
+        }
+
+        row.truncate(size);
+        input.push_str(&row);
+        input.push('\n');
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let input = generate(&mut Rng::new(1), 60);
+
+        assert_eq!(input, generate(&mut Rng::new(1), 60));
+        assert_eq!(input.lines().count(), 60);
+        assert!(input.lines().all(|line| line.len() == 60));
+        assert!(crate::try_part1(&input).is_ok());
+        assert!(crate::try_part2(&input).is_ok());
+    }
+}
@@ -1,306 +1,520 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::Display,
+};
 
-const SYMBOLS: &'static str = "@#$%^&*/-+=";
+use common::{checked_mul, checked_sum, Answer, Explanation, Position, Solution, Wide};
+use grid::ParseGridError;
+use log::{debug, trace};
 
-struct EngineSchematic {
-    vec: Vec<char>,
-    rows: usize,
-    cols: usize,
+mod generate;
+#[cfg(test)]
+mod proptests;
+
+pub use generate::generate;
+pub use grid::{Grid, Pos};
+
+/// Error from reading an engine schematic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The schematic has no rows
+    Empty { position: Position },
+    /// A row that isn't as wide as the first row
+    RowLength {
+        position: Position,
+        expected: usize,
+        found: usize,
+    },
+    /// A part number, gear ratio or sum too large for [`Wide`] without
+    /// the `bigint` feature
+    Overflow { position: Position },
 }
 
-impl EngineSchematic {
-    fn new(height: usize, width: usize) -> Self {
-        EngineSchematic {
-            vec: vec!['.'; height * width],
-            rows: height,
-            cols: width,
+impl ParseError {
+    pub fn position(&self) -> Position {
+        match self {
+            ParseError::Empty { position }
+            | ParseError::RowLength { position, .. }
+            | ParseError::Overflow { position } => *position,
         }
     }
+}
 
-    fn new_with_values(height: usize, width: usize, values: Vec<&str>) -> Self {
-        let mut engine_schematic = EngineSchematic::new(height, width);
-
-        for (row, str) in values.iter().enumerate() {
-            for (col, c) in str.char_indices() {
-                engine_schematic.set_cell(row, col, c);
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Empty { position } => write!(f, "{position}: expected a schematic row"),
+            ParseError::RowLength {
+                position,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{position}: expected a row of {expected} cells, found {found}"
+            ),
+            ParseError::Overflow { position } => {
+                write!(
+                    f,
+                    "{position}: too large to solve without the `bigint` feature"
+                )
             }
         }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Overflow at the 0-based `pos` in the schematic
+fn overflow(pos: Pos) -> ParseError {
+    ParseError::Overflow {
+        position: Position {
+            line: pos.row + 1,
+            column: pos.col + 1,
+        },
+    }
+}
+
+/// Symbols that make a number next to them a part number
+pub const SYMBOLS: &str = "@#$%^&*/-+=";
+
+/// Grid of digits, symbols and `.` for empty cells. With the `serde`
+/// feature it's stored as its rows of text
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Vec<String>", into = "Vec<String>")
+)]
+pub struct EngineSchematic {
+    grid: Grid<char>,
+}
 
+impl TryFrom<Vec<String>> for EngineSchematic {
+    type Error = ParseError;
+
+    fn try_from(rows: Vec<String>) -> Result<Self, Self::Error> {
+        Day3::parse(&rows.join("\n"))
+    }
+}
+
+impl From<EngineSchematic> for Vec<String> {
+    fn from(engine_schematic: EngineSchematic) -> Self {
         engine_schematic
+            .grid
+            .iter_rows()
+            .map(|row| row.iter().collect())
+            .collect()
     }
+}
 
-    fn get_by_index(&self, row: usize, col: usize) -> &char {
-        let idx = self.cols * row;
-        &self.vec[idx + col]
+impl EngineSchematic {
+    pub fn new(grid: Grid<char>) -> Self {
+        EngineSchematic { grid }
     }
 
-    fn set_cell(&mut self, row: usize, col: usize, value: char) {
-        let idx = self.cols * row;
-        self.vec[idx + col] = value;
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
     }
 
-    fn adjacent_symbol(&self, row: usize, col: usize, num_len: usize, symbols: &str) -> bool {
-        for num_pos in 0..num_len {
-            if let Some(col) = col.checked_sub(num_pos) {
-                if self.in_bounds(row.checked_sub(1), Some(col)) {
-                    // above
-                    if symbols.contains(*self.get_by_index(row - 1, col)) {
-                        return true;
-                    }
-                }
-                if self.in_bounds(row.checked_sub(1), Some(col + 1)) {
-                    // upper right
-                    if symbols.contains(*self.get_by_index(row - 1, col + 1)) {
-                        return true;
-                    }
-                }
-                if self.in_bounds(Some(row), Some(col + 1)) {
-                    // right
-                    if symbols.contains(*self.get_by_index(row, col + 1)) {
-                        return true;
-                    }
-                }
-                if self.in_bounds(Some(row + 1), Some(col + 1)) {
-                    // lower right
-                    if symbols.contains(*self.get_by_index(row + 1, col + 1)) {
-                        return true;
-                    }
-                }
-                if self.in_bounds(Some(row + 1), Some(col)) {
-                    // below
-                    if symbols.contains(*self.get_by_index(row + 1, col)) {
-                        return true;
-                    }
-                }
-                if self.in_bounds(Some(row + 1), col.checked_sub(1)) {
-                    // lower left
-                    if symbols.contains(*self.get_by_index(row + 1, col - 1)) {
-                        return true;
-                    }
-                }
-                if self.in_bounds(Some(row), col.checked_sub(1)) {
-                    // left
-                    if symbols.contains(*self.get_by_index(row, col - 1)) {
-                        return true;
-                    }
-                }
-                if self.in_bounds(row.checked_sub(1), col.checked_sub(1)) {
-                    // upper left
-                    if symbols.contains(*self.get_by_index(row - 1, col - 1)) {
-                        return true;
+    /// Position of the first symbol next to the number that ends at
+    /// `row`, `col`
+    fn find_symbol(&self, row: usize, col: usize, num_len: usize, symbols: &str) -> Option<Pos> {
+        self.find_symbols(row, col, num_len, symbols).next()
+    }
+
+    /// Every number in the schematic as the position of its first
+    /// digit, its number of digits and its value
+    fn numbers(&self) -> Result<Vec<(Pos, usize, Wide)>, ParseError> {
+        let mut numbers = vec![];
+
+        for (row, cells) in self.grid.iter_rows().enumerate() {
+            let mut start = None;
+            for col in 0..=cells.len() {
+                let is_digit = cells.get(col).is_some_and(char::is_ascii_digit);
+                match (is_digit, start) {
+                    (true, None) => start = Some(col),
+                    (false, Some(first)) => {
+                        let pos = Pos::new(row, first);
+                        let number = cells[first..col].iter().collect::<String>();
+                        // digits only, so the number can only be too long
+                        let number = number.parse().map_err(|_| overflow(pos))?;
+                        numbers.push((pos, col - first, number));
+                        start = None;
                     }
+                    _ => {}
                 }
             }
         }
-        false
+
+        Ok(numbers)
     }
 
-    fn find_gears(&self, row: usize, col: usize, num_len: usize, symbols: &str) -> String {
-        for num_pos in 0..num_len {
-            if let Some(col) = col.checked_sub(num_pos) {
-                if self.in_bounds(row.checked_sub(1), Some(col)) {
-                    // above
-                    if symbols.contains(*self.get_by_index(row - 1, col)) {
-                        return format!("{},{}", row - 1, col);
-                    }
-                }
-                if self.in_bounds(row.checked_sub(1), Some(col + 1)) {
-                    // upper right
-                    if symbols.contains(*self.get_by_index(row - 1, col + 1)) {
-                        return format!("{},{}", row - 1, col + 1);
-                    }
-                }
-                if self.in_bounds(Some(row), Some(col + 1)) {
-                    // right
-                    if symbols.contains(*self.get_by_index(row, col + 1)) {
-                        return format!("{},{}", row, col + 1);
-                    }
-                }
-                if self.in_bounds(Some(row + 1), Some(col + 1)) {
-                    // lower right
-                    if symbols.contains(*self.get_by_index(row + 1, col + 1)) {
-                        return format!("{},{}", row + 1, col + 1);
-                    }
-                }
-                if self.in_bounds(Some(row + 1), Some(col)) {
-                    // below
-                    if symbols.contains(*self.get_by_index(row + 1, col)) {
-                        return format!("{},{}", row + 1, col);
-                    }
-                }
-                if self.in_bounds(Some(row + 1), col.checked_sub(1)) {
-                    // lower left
-                    if symbols.contains(*self.get_by_index(row + 1, col - 1)) {
-                        return format!("{},{}", row + 1, col - 1);
-                    }
-                }
-                if self.in_bounds(Some(row), col.checked_sub(1)) {
-                    // left
-                    if symbols.contains(*self.get_by_index(row, col - 1)) {
-                        return format!("{},{}", row, col - 1);
-                    }
-                }
-                if self.in_bounds(row.checked_sub(1), col.checked_sub(1)) {
-                    // upper left
-                    if symbols.contains(*self.get_by_index(row - 1, col - 1)) {
-                        return format!("{},{}", row - 1, col - 1);
-                    }
-                }
+    /// Every gear, a `*` next to exactly two part numbers, with the
+    /// numbers it joins, in reading order
+    // `Wide` is only `Copy` without the `bigint` feature, so clone numbers
+    #[allow(clippy::clone_on_copy)]
+    fn gears(&self) -> Result<Vec<(Pos, [Wide; 2])>, ParseError> {
+        let mut gears: HashMap<Pos, Vec<Wide>> = HashMap::new();
+
+        for (start, len, number) in self.numbers()? {
+            let end = start.col + len - 1;
+            for gear_pos in self.find_symbols(start.row, end, len, "*") {
+                gears.entry(gear_pos).or_default().push(number.clone());
             }
         }
-        "".to_string()
+
+        let mut gears: Vec<(Pos, [Wide; 2])> = gears
+            .into_iter()
+            .filter_map(|(pos, numbers)| Some((pos, numbers.try_into().ok()?)))
+            .collect();
+        gears.sort_by_key(|&(pos, _)| (pos.row, pos.col));
+
+        Ok(gears)
     }
 
-    fn in_bounds(&self, row: Option<usize>, col: Option<usize>) -> bool {
-        if let (Some(row), Some(col)) = (row, col) {
-            row < self.rows && col < self.cols
-        } else {
-            false
-        }
+    /// Positions of every symbol from `symbols` next to the number of
+    /// `num_len` digits that ends at `row`, `col`, each only once
+    pub fn find_symbols<'a>(
+        &'a self,
+        row: usize,
+        col: usize,
+        num_len: usize,
+        symbols: &'a str,
+    ) -> impl Iterator<Item = Pos> + 'a {
+        let mut seen = HashSet::new();
+
+        (0..num_len)
+            .filter_map(move |num_pos| col.checked_sub(num_pos))
+            .flat_map(move |col| self.grid.neighbours8(Pos::new(row, col)))
+            .filter(move |&pos| symbols.contains(self.grid[pos]) && seen.insert(pos))
     }
 }
 
+/// Canonical text of the schematic, each row on its own line
 impl Display for EngineSchematic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in 0..self.rows {
-            for col in 0..self.cols {
-                write!(f, "{} ", self.get_by_index(row, col))?;
+        for row in self.grid.iter_rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
         Ok(())
     }
 }
 
-pub fn part1(input: &str) -> u32 {
-    let lines: Vec<&str> = input.lines().collect();
-    let height = lines.len();
-    let width = lines[0].len();
-    let engine_schematic = EngineSchematic::new_with_values(height, width, lines);
-    let mut num_str = String::new();
-    let mut sum = 0;
-    let mut nums_counting = vec![];
-
-    for row in 0..engine_schematic.rows {
-        for col in 0..engine_schematic.cols {
-            if engine_schematic.get_by_index(row, col).is_numeric() {
-                num_str.push(*engine_schematic.get_by_index(row, col));
-            } else {
-                if !num_str.is_empty() {
-                    let (row, col) = match col {
-                        0 => (row, engine_schematic.cols - 1),
-                        _ => (row, col - 1),
-                    };
-                    if engine_schematic.adjacent_symbol(row, col, num_str.len(), SYMBOLS) {
-                        println!("{} has adjacent symbol", num_str);
-                        let to_add = num_str.parse::<u32>().unwrap();
-                        nums_counting.push(to_add);
-                        sum = sum + to_add;
-                    } else {
-                        println!("{} doesn't have adjacent symbol", num_str);
-                    }
-                }
-                num_str.clear();
-            }
-        }
+/// Error for the 0-based `row` having `found` cells rather than `expected`
+fn row_length(row: usize, expected: usize, found: usize) -> ParseError {
+    ParseError::RowLength {
+        position: Position {
+            line: row + 1,
+            column: found.min(expected) + 1,
+        },
+        expected,
+        found,
+    }
+}
+
+/// Part number and the symbol it was counted for, as a step of
+/// explaining part 1. Lines and columns are 1-based
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartNumber {
+    pub line: usize,
+    pub column: usize,
+    #[cfg_attr(feature = "serde", serde(with = "common::wide_decimal"))]
+    pub number: Wide,
+    pub symbol: char,
+    pub symbol_line: usize,
+    pub symbol_column: usize,
+}
+
+/// Gear and the two part numbers it joins, as a step of explaining
+/// part 2. Lines and columns are 1-based
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gear {
+    pub line: usize,
+    pub column: usize,
+    #[cfg_attr(feature = "serde", serde(with = "common::wide_decimal"))]
+    pub first: Wide,
+    #[cfg_attr(feature = "serde", serde(with = "common::wide_decimal"))]
+    pub second: Wide,
+    #[cfg_attr(feature = "serde", serde(with = "common::wide_decimal"))]
+    pub ratio: Wide,
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = EngineSchematic;
 
-        // edge case at the end of a line
-        if !num_str.is_empty() {
-            if engine_schematic.adjacent_symbol(
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        let grid = input.parse::<Grid<char>>().map_err(|err| match err {
+            ParseGridError::RowLength {
                 row,
-                engine_schematic.cols - 1,
-                num_str.len(),
-                SYMBOLS,
-            ) {
-                println!("{} has adjacent symbol", num_str);
-                let to_add = num_str.parse::<u32>().unwrap();
-                nums_counting.push(to_add);
-                sum = sum + to_add;
+                expected,
+                found,
+            } => row_length(row, expected, found),
+            _ => ParseError::Empty {
+                position: Position { line: 1, column: 1 },
+            },
+        })?;
+
+        Ok(EngineSchematic { grid })
+    }
+
+    fn part1(engine_schematic: &Self::Input) -> Result<Answer, Self::Error> {
+        let mut part_numbers = vec![];
+
+        for (start, len, number) in engine_schematic.numbers()? {
+            let end = start.col + len - 1;
+            if engine_schematic
+                .find_symbol(start.row, end, len, SYMBOLS)
+                .is_some()
+            {
+                trace!("{number} has an adjacent symbol");
+                part_numbers.push((start, number));
             } else {
-                println!("{} doesn't have adjacent symbol", num_str);
+                trace!("{number} doesn't have an adjacent symbol");
             }
         }
 
-        num_str.clear();
+        debug!("{} part numbers", part_numbers.len());
+
+        let sum = checked_sum(part_numbers.iter().map(|(_, number)| number))
+            .map_err(|idx| overflow(part_numbers[idx].0))?;
+        Ok(sum.into())
     }
 
-    println!("{}", nums_counting.len());
+    fn part2(engine_schematic: &Self::Input) -> Result<Option<Answer>, Self::Error> {
+        let gears = engine_schematic.gears()?;
+        let ratios = gears
+            .iter()
+            .map(|(gear_pos, [first, second])| {
+                trace!("gear at {gear_pos} joins {first} and {second}");
+                checked_mul(first, second).ok_or_else(|| overflow(*gear_pos))
+            })
+            .collect::<Result<Vec<Wide>, ParseError>>()?;
 
-    sum
+        let sum = checked_sum(ratios).map_err(|idx| overflow(gears[idx].0))?;
+        Ok(Some(sum.into()))
+    }
 }
 
-pub fn part2(input: &str) -> u32 {
-    let lines: Vec<&str> = input.lines().collect();
-    let height = lines.len();
-    let width = lines[0].len();
-    let engine_schematic = EngineSchematic::new_with_values(height, width, lines);
-    let mut num_str = String::new();
-    let mut sum = 0;
-    let mut gears = HashMap::new();
-
-    for row in 0..engine_schematic.rows {
-        for col in 0..engine_schematic.cols {
-            if engine_schematic.get_by_index(row, col).is_numeric() {
-                num_str.push(*engine_schematic.get_by_index(row, col));
-            } else {
-                if !num_str.is_empty() {
-                    let (row, col) = match col {
-                        0 => (row, engine_schematic.cols - 1),
-                        _ => (row, col - 1),
-                    };
-                    let gear_pos = engine_schematic.find_gears(row, col, num_str.len(), "*");
-                    if !gear_pos.is_empty() {
-                        let gear = num_str.parse::<u32>().unwrap();
-                        if gears.contains_key(&gear_pos) {
-                            let other_gear = gears.get(&gear_pos).unwrap();
-                            println!("{} {}", gear, other_gear);
-                            sum = sum + (other_gear * gear);
-                        } else {
-                            gears.insert(gear_pos, gear);
-                        }
-                    }
-                }
-                num_str.clear();
-            }
-        }
+pub fn try_part1(input: &str) -> Result<Answer, ParseError> {
+    Day3::part1(&Day3::parse(input)?)
+}
 
-        if !num_str.is_empty() {
-            let gear_pos =
-                engine_schematic.find_gears(row, engine_schematic.cols - 1, num_str.len(), "*");
-            if !gear_pos.is_empty() {
-                let gear = num_str.parse::<u32>().unwrap();
-                if gears.contains_key(&gear_pos) {
-                    let other_gear = gears.get(&gear_pos).unwrap();
-                    sum = sum + (other_gear * gear);
-                } else {
-                    gears.insert(gear_pos, gear);
-                }
-            }
-        }
+pub fn try_part2(input: &str) -> Result<Answer, ParseError> {
+    let answer = Day3::part2(&Day3::parse(input)?)?;
+    Ok(answer.expect("part 2 is solved"))
+}
+
+pub fn part1(input: &str) -> Answer {
+    try_part1(input).unwrap()
+}
 
-        num_str.clear();
+pub fn part2(input: &str) -> Answer {
+    try_part2(input).unwrap()
+}
+
+/// Part 1 along with every part number and the symbol next to it
+pub fn explain_part1(input: &str) -> Result<Explanation<PartNumber>, ParseError> {
+    let engine_schematic = Day3::parse(input)?;
+
+    let steps: Vec<PartNumber> = engine_schematic
+        .numbers()?
+        .into_iter()
+        .filter_map(|(start, len, number)| {
+            let end = start.col + len - 1;
+            let symbol = engine_schematic.find_symbol(start.row, end, len, SYMBOLS)?;
+            Some(PartNumber {
+                line: start.row + 1,
+                column: start.col + 1,
+                number,
+                symbol: engine_schematic.grid[symbol],
+                symbol_line: symbol.row + 1,
+                symbol_column: symbol.col + 1,
+            })
+        })
+        .collect();
+
+    let sum = checked_sum(steps.iter().map(|step| &step.number))
+        .map_err(|idx| overflow(Pos::new(steps[idx].line - 1, steps[idx].column - 1)))?;
+    Ok(Explanation::new(sum, steps))
+}
+
+/// Part 2 along with every gear, in reading order
+pub fn explain_part2(input: &str) -> Result<Explanation<Gear>, ParseError> {
+    let steps = Day3::parse(input)?
+        .gears()?
+        .into_iter()
+        .map(|(pos, [first, second])| {
+            Ok(Gear {
+                line: pos.row + 1,
+                column: pos.col + 1,
+                ratio: checked_mul(&first, &second).ok_or_else(|| overflow(pos))?,
+                first,
+                second,
+            })
+        })
+        .collect::<Result<Vec<Gear>, ParseError>>()?;
+
+    let sum = checked_sum(steps.iter().map(|step| &step.ratio))
+        .map_err(|idx| overflow(Pos::new(steps[idx].line - 1, steps[idx].column - 1)))?;
+    Ok(Explanation::new(sum, steps))
+}
+
+/// Every row that isn't as wide as the first, where parsing stops at the
+/// first
+pub fn lint(input: &str) -> Vec<ParseError> {
+    let expected = input.lines().next().map_or(0, |line| line.chars().count());
+    let mut errors: Vec<ParseError> = input
+        .lines()
+        .enumerate()
+        .skip(1)
+        .filter_map(|(row, line)| {
+            let found = line.chars().count();
+            (found != expected).then(|| row_length(row, expected, found))
+        })
+        .collect();
+
+    if let Err(err) = Day3::parse(input) {
+        if !errors.contains(&err) {
+            errors.push(err);
+        }
     }
 
-    sum
+    errors
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = include_str!("../input.txt");
+    #[test]
+    fn display_test() {
+        let input = format!("{}\n", include_str!("../examples/example.txt"));
+
+        assert_eq!(Day3::parse(&input).unwrap().to_string(), input);
+    }
+
+    #[test]
+    fn model_test() {
+        let engine_schematic = Day3::parse("467.\n...*\n..35").unwrap();
+
+        assert_eq!(
+            engine_schematic,
+            EngineSchematic::new(Grid::from_vec(3, 4, "467....*..35".chars().collect()).unwrap())
+        );
+        assert_eq!(engine_schematic.grid()[Pos::new(1, 3)], '*');
+        assert_eq!(
+            engine_schematic
+                .find_symbols(0, 2, 3, SYMBOLS)
+                .collect::<Vec<_>>(),
+            [Pos::new(1, 3)]
+        );
+        assert_eq!(engine_schematic.find_symbols(2, 3, 2, "#").count(), 0);
+    }
 
     #[test]
-    fn part1_test() {
-        let result = part1(INPUT);
+    fn lint_test() {
+        let errors: Vec<String> = lint("467..\n...*\n..35.\n.......")
+            .iter()
+            .map(ParseError::to_string)
+            .collect();
 
-        assert_eq!(result, 4361);
+        assert_eq!(
+            errors,
+            [
+                "2:5: expected a row of 5 cells, found 4",
+                "4:6: expected a row of 5 cells, found 7",
+            ]
+        );
+        assert_eq!(lint("")[0].to_string(), "1:1: expected a schematic row");
+        assert_eq!(lint(include_str!("../examples/example.txt")), []);
     }
 
     #[test]
-    fn part2_test() {
-        let result = part2(INPUT);
+    fn overflow_test() {
+        // a part number past u128, and a gear joining two past u64
+        let long = "1".repeat(40);
+        let wide = "9".repeat(20);
+        let part_number = format!("{long}\n*{}", ".".repeat(39));
+        let gear = format!("{wide}.\n{}*\n{wide}.", ".".repeat(20));
 
-        assert_eq!(result, 467835);
+        #[cfg(not(feature = "bigint"))]
+        {
+            assert_eq!(
+                try_part1(&part_number).unwrap_err().to_string(),
+                "1:1: too large to solve without the `bigint` feature"
+            );
+            assert_eq!(
+                try_part2(&gear).unwrap_err().to_string(),
+                "2:21: too large to solve without the `bigint` feature"
+            );
+        }
+        #[cfg(feature = "bigint")]
+        {
+            let long = long.parse::<Wide>().unwrap();
+            let wide = wide.parse::<Wide>().unwrap();
+            assert_eq!(try_part1(&part_number).unwrap(), Answer::from(long));
+            assert_eq!(try_part2(&gear).unwrap(), Answer::from(&wide * &wide));
+        }
+    }
+
+    #[test]
+    fn explain_test() {
+        let input = "467..114..\n...*......\n..35..633.\n......#...";
+        let explanation = explain_part1(input).unwrap();
+
+        assert_eq!(explanation.answer, Answer::from(1135));
+        assert_eq!(
+            explanation.steps[0],
+            PartNumber {
+                line: 1,
+                column: 1,
+                number: Wide::from(467u32),
+                symbol: '*',
+                symbol_line: 2,
+                symbol_column: 4,
+            }
+        );
+        assert_eq!(explanation.steps[2].symbol, '#');
+        assert_eq!(
+            explain_part2(input).unwrap().steps,
+            [Gear {
+                line: 2,
+                column: 4,
+                first: Wide::from(467u32),
+                second: Wide::from(35u32),
+                ratio: Wide::from(16345u32),
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            try_part1(""),
+            Err(ParseError::Empty {
+                position: Position { line: 1, column: 1 }
+            })
+        );
+        assert_eq!(
+            try_part2("467..\n...*\n..35.").unwrap_err().to_string(),
+            "2:5: expected a row of 5 cells, found 4"
+        );
     }
 }
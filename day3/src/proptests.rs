@@ -0,0 +1,108 @@
+use proptest::prelude::*;
+
+use super::*;
+
+/// Every number in the schematic with its row and column span
+fn numbers(rows: &[&str]) -> Vec<(u32, usize, usize, usize)> {
+    let mut numbers = vec![];
+
+    for (row, line) in rows.iter().enumerate() {
+        let bytes = line.as_bytes();
+        let mut col = 0;
+
+        while col < bytes.len() {
+            if bytes[col].is_ascii_digit() {
+                let start = col;
+                while col < bytes.len() && bytes[col].is_ascii_digit() {
+                    col += 1;
+                }
+                numbers.push((line[start..col].parse().unwrap(), row, start, col));
+            } else {
+                col += 1;
+            }
+        }
+    }
+
+    numbers
+}
+
+/// Cells around a number, clamped to the schematic
+fn surrounding(rows: &[&str], row: usize, start: usize, end: usize) -> Vec<(usize, usize)> {
+    let mut cells = vec![];
+
+    let last_row = (row + 1).min(rows.len() - 1);
+    for (r, line) in rows
+        .iter()
+        .enumerate()
+        .take(last_row + 1)
+        .skip(row.saturating_sub(1))
+    {
+        for c in start.saturating_sub(1)..=end.min(line.len() - 1) {
+            cells.push((r, c));
+        }
+    }
+
+    cells
+}
+
+fn naive_part1(input: &str) -> u32 {
+    let rows: Vec<&str> = input.lines().collect();
+
+    numbers(&rows)
+        .into_iter()
+        .filter(|&(_, row, start, end)| {
+            surrounding(&rows, row, start, end)
+                .into_iter()
+                .any(|(r, c)| SYMBOLS.contains(rows[r].as_bytes()[c] as char))
+        })
+        .map(|(number, ..)| number)
+        .sum()
+}
+
+/// Sum of the gear ratios of every `*` next to exactly two numbers
+fn naive_part2(input: &str) -> u32 {
+    let rows: Vec<&str> = input.lines().collect();
+    let mut gears: HashMap<(usize, usize), Vec<u32>> = HashMap::new();
+
+    for (number, row, start, end) in numbers(&rows) {
+        for (r, c) in surrounding(&rows, row, start, end) {
+            if rows[r].as_bytes()[c] == b'*' {
+                gears.entry((r, c)).or_default().push(number);
+            }
+        }
+    }
+
+    gears
+        .values()
+        .filter(|numbers| numbers.len() == 2)
+        .map(|numbers| numbers[0] * numbers[1])
+        .sum()
+}
+
+proptest! {
+    #[test]
+    fn parse_never_panics(input in "\\PC*") {
+        let _ = Day3::parse(&input);
+    }
+
+    #[test]
+    fn part1_matches_naive_scan(seed: u64, size in 1..40usize) {
+        let input = generate(&mut common::Rng::new(seed), size);
+
+        prop_assert_eq!(part1(&input), naive_part1(&input));
+    }
+
+    #[test]
+    fn part2_matches_naive_scan(seed: u64, size in 1..40usize) {
+        let input = generate(&mut common::Rng::new(seed), size);
+
+        prop_assert_eq!(part2(&input), naive_part2(&input));
+    }
+
+    #[test]
+    fn display_round_trips(seed: u64, size in 1..40usize) {
+        let input = generate(&mut common::Rng::new(seed), size);
+
+        prop_assert_eq!(Day3::parse(&input).unwrap().to_string(), input);
+    }
+}
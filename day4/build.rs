@@ -0,0 +1,3 @@
+fn main() {
+    examples::build_tests();
+}
@@ -0,0 +1,64 @@
+use std::fmt::Write;
+
+use common::Rng;
+
+const WINNING_NUMBERS: usize = 10;
+const NUMBERS: usize = 25;
+
+/// Pile of `size` scratchcards with ten winning numbers and twenty five
+/// numbers you have, all below 100. Most cards win nothing, so the copies
+/// won in part 2 stay bounded however many cards there are
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    let width = size.to_string().len().max(3);
+
+    for id in 1..=size {
+        let mut pool: Vec<u32> = (1..100).collect();
+        rng.shuffle(&mut pool);
+        let (winning_numbers, others) = pool.split_at(WINNING_NUMBERS);
+
+        let matches = match rng.one_in(6) {
+            true => rng.index(WINNING_NUMBERS) + 1,
+            false => 0,
+        };
+        let mut numbers: Vec<u32> = winning_numbers[..matches]
+            .iter()
+            .chain(&others[..NUMBERS - matches])
+            .copied()
+            .collect();
+        rng.shuffle(&mut numbers);
+
+        writeln!(
+            input,
+            "Card {id:>width$}: {} | {}",
+            format_numbers(winning_numbers),
+            format_numbers(&numbers)
+        )
+        .unwrap();
+    }
+
+    input
+}
+
+fn format_numbers(numbers: &[u32]) -> String {
+    numbers
+        .iter()
+        .map(|number| format!("{number:>2}"))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let input = generate(&mut Rng::new(1), 100);
+
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        assert_eq!(input.lines().count(), 100);
+        assert!(crate::try_part1(&input).is_ok());
+        assert!(crate::try_part2(&input).is_ok());
+    }
+}
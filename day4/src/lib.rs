@@ -1,20 +1,160 @@
-use std::str::FromStr;
+use std::{cmp, collections::VecDeque, error::Error, fmt::Display, io::BufRead, str::FromStr};
 
-#[derive(Debug)]
-struct Card {
+use common::{
+    checked_add, checked_pow, checked_sum, for_each_line, par, Answer, Explanation, Position,
+    ReadError, Solution, Wide,
+};
+
+mod generate;
+mod naive;
+#[cfg(test)]
+mod proptests;
+
+pub use generate::generate;
+pub use naive::{generate_small, naive_part1, naive_part2};
+
+/// Error from reading the pile of scratchcards
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Syntax that a card line is missing
+    Expected {
+        position: Position,
+        expected: &'static str,
+    },
+    /// A card id or card number that isn't a number
+    InvalidNumber { position: Position, text: String },
+    /// Points or copies won on a card too many for [`Wide`] without the
+    /// `bigint` feature
+    Overflow { position: Position },
+}
+
+impl ParseError {
+    pub fn position(&self) -> Position {
+        match self {
+            ParseError::Expected { position, .. }
+            | ParseError::InvalidNumber { position, .. }
+            | ParseError::Overflow { position } => *position,
+        }
+    }
+
+    fn on_line(mut self, lines: usize) -> Self {
+        match &mut self {
+            ParseError::Expected { position, .. }
+            | ParseError::InvalidNumber { position, .. }
+            | ParseError::Overflow { position } => *position = position.on_line(lines),
+        }
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Expected { position, expected } => {
+                write!(f, "{position}: expected {expected}")
+            }
+            ParseError::InvalidNumber { position, text } => {
+                write!(f, "{position}: expected a number, found `{text}`")
+            }
+            ParseError::Overflow { position } => {
+                write!(
+                    f,
+                    "{position}: too large to solve without the `bigint` feature"
+                )
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Overflow on the card at `idx` in the pile
+fn overflow(idx: usize) -> ParseError {
+    ParseError::Overflow {
+        position: Position {
+            line: idx + 1,
+            column: 1,
+        },
+    }
+}
+
+/// A scratchcard's id, its winning numbers and the numbers you have
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "CardData", into = "CardData")
+)]
+pub struct Card {
     id: u32,
     winning_numbers: Vec<u32>,
     numbers: Vec<u32>,
     card_winning_nums: u32,
-    card_points: u32,
-    copies: u32,
+}
+
+/// Fields of a card that aren't worked out from the others, which is
+/// how it's serialized
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CardData {
+    id: u32,
+    winning_numbers: Vec<u32>,
+    numbers: Vec<u32>,
+}
+
+#[cfg(feature = "serde")]
+impl From<CardData> for Card {
+    fn from(data: CardData) -> Self {
+        Card::new(data.id, data.winning_numbers, data.numbers)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Card> for CardData {
+    fn from(card: Card) -> Self {
+        CardData {
+            id: card.id,
+            winning_numbers: card.winning_numbers,
+            numbers: card.numbers,
+        }
+    }
 }
 
 impl Card {
-    fn determine_card_points(&mut self) {
-        self.count_winning_numbers();
-        if self.card_winning_nums != 0 {
-            self.card_points = u32::pow(2, self.card_winning_nums - 1);
+    pub fn new(id: u32, winning_numbers: Vec<u32>, numbers: Vec<u32>) -> Self {
+        let mut card = Card {
+            id,
+            winning_numbers,
+            numbers,
+            card_winning_nums: 0,
+        };
+        card.count_winning_numbers();
+        card
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn winning_numbers(&self) -> &[u32] {
+        &self.winning_numbers
+    }
+
+    pub fn numbers(&self) -> &[u32] {
+        &self.numbers
+    }
+
+    /// How many of the numbers you have are winning numbers
+    pub fn matches(&self) -> u32 {
+        self.card_winning_nums
+    }
+
+    /// One point for the first match, doubled for every match after it.
+    /// `None` when that's too many points for [`Wide`]
+    pub fn points(&self) -> Option<Wide> {
+        match self.card_winning_nums {
+            0 => Some(Wide::from(0u32)),
+            matches => checked_pow(2, matches - 1),
         }
     }
 
@@ -23,7 +163,7 @@ impl Card {
 
         for num in &self.numbers {
             if self.winning_numbers.contains(num) {
-                count = count + 1;
+                count += 1;
             }
         }
 
@@ -31,89 +171,432 @@ impl Card {
     }
 }
 
+/// Canonical text of a card, with every number right aligned to two
+/// characters. A width, as in `{card:3}`, right aligns the id to it like
+/// the real inputs do
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Card {:>1$}:", self.id, f.width().unwrap_or(0))?;
+        for number in &self.winning_numbers {
+            write!(f, " {number:>2}")?;
+        }
+        write!(f, " |")?;
+        for number in &self.numbers {
+            write!(f, " {number:>2}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Creates a Card struct from a card string line
 /// Example: "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
 impl FromStr for Card {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (card_id, numbers) = s.split_once(':').unwrap();
-        let (_, id) = card_id.split_once(' ').unwrap();
+        let (card_id, numbers) = s.split_once(':').ok_or_else(|| ParseError::Expected {
+            position: Position::end_of(s, s),
+            expected: "':' after the card id",
+        })?;
+        let (_, id) = card_id
+            .split_once(' ')
+            .ok_or_else(|| ParseError::Expected {
+                position: Position::within(s, card_id),
+                expected: "a card id",
+            })?;
 
-        let (winning_numbers, numbers) = numbers.trim().split_once('|').unwrap();
+        let (winning_numbers, numbers) =
+            numbers
+                .trim()
+                .split_once('|')
+                .ok_or_else(|| ParseError::Expected {
+                    position: Position::end_of(s, s),
+                    expected: "'|' between the winning numbers and the numbers you have",
+                })?;
 
-        let winning_numbers: Vec<u32> = winning_numbers
+        let winning_numbers = winning_numbers
             .split_whitespace()
-            .map(|number| number.parse::<u32>().unwrap())
-            .collect();
+            .map(|number| parse_number(s, number))
+            .collect::<Result<Vec<u32>, ParseError>>()?;
 
-        let numbers: Vec<u32> = numbers
+        let numbers = numbers
             .split_whitespace()
-            .map(|number| number.parse::<u32>().unwrap())
-            .collect();
+            .map(|number| parse_number(s, number))
+            .collect::<Result<Vec<u32>, ParseError>>()?;
 
-        Ok(Card {
-            id: id.trim().parse::<u32>().unwrap(),
+        Ok(Card::new(
+            parse_number(s, id.trim())?,
             winning_numbers,
             numbers,
-            card_winning_nums: 0,
-            card_points: 0,
-            copies: 1,
-        })
+        ))
     }
 }
 
-pub fn part1(input: &str) -> u32 {
-    let total_card_points: u32 = input
-        .lines()
-        .map(|line| {
-            let mut card = line.parse::<Card>().unwrap();
-            card.determine_card_points();
-            card.card_points
+fn parse_number(line: &str, number: &str) -> Result<u32, ParseError> {
+    number
+        .parse::<u32>()
+        .map_err(|_| ParseError::InvalidNumber {
+            position: Position::within(line, number),
+            text: number.to_string(),
         })
-        .sum();
+}
 
-    total_card_points
+/// Numbers a card matched and the points they scored, as a step of
+/// explaining part 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CardPoints {
+    pub card: u32,
+    pub matching: Vec<u32>,
+    #[cfg_attr(feature = "serde", serde(with = "common::wide_decimal"))]
+    pub points: Wide,
 }
 
-pub fn part2(input: &str) -> u32 {
-    let mut cards: Vec<Card> = input
-        .lines()
-        .map(|line| {
-            let mut card = line.parse::<Card>().unwrap();
-            card.count_winning_numbers();
-            card
+/// How many copies of a card were won, as a step of explaining part 2
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CardCopies {
+    pub card: u32,
+    pub matches: u32,
+    #[cfg_attr(feature = "serde", serde(with = "common::wide_decimal"))]
+    pub copies: Wide,
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Card>;
+
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        let lines: Vec<&str> = input.lines().collect();
+        par::try_map(&lines, |idx, line| {
+            line.parse::<Card>().map_err(|err| err.on_line(idx))
         })
-        .collect();
+    }
+
+    fn part1(cards: &Self::Input) -> Result<Answer, Self::Error> {
+        let points = par::map(cards, Card::points)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, points)| points.ok_or_else(|| overflow(idx)))
+            .collect::<Result<Vec<Wide>, ParseError>>()?;
 
-    for idx in 0..cards.len() {
-        let num_of_winning_cards = cards[idx].card_winning_nums as usize;
-        let copies_curr_card = cards[idx].copies;
-        let winning_copies = &mut cards[(idx + 1)..(idx + num_of_winning_cards + 1)];
+        let sum = checked_sum(points).map_err(overflow)?;
+        Ok(sum.into())
+    }
+
+    fn part2(cards: &Self::Input) -> Result<Option<Answer>, Self::Error> {
+        let sum = checked_sum(copies(cards)?).map_err(overflow)?;
+
+        Ok(Some(sum.into()))
+    }
+}
+
+/// How many copies of each card you end up with
+fn copies(cards: &[Card]) -> Result<Vec<Wide>, ParseError> {
+    let mut copies: Vec<Wide> = vec![Wide::from(1u32); cards.len()];
+
+    for (idx, card) in cards.iter().enumerate() {
+        let num_of_winning_cards = card.card_winning_nums as usize;
+        // cards never make you copy past the end of the table
+        let last_copy = cmp::min(idx + num_of_winning_cards + 1, copies.len());
+        let (scratched, to_come) = copies.split_at_mut(idx + 1);
+        let copies_curr_card = &scratched[idx];
+        let winning_copies = &mut to_come[..(last_copy - idx - 1)];
 
         for winning_copy in winning_copies.iter_mut() {
-            winning_copy.copies = winning_copy.copies + copies_curr_card;
+            *winning_copy =
+                checked_add(winning_copy, copies_curr_card).ok_or_else(|| overflow(idx))?;
         }
     }
 
-    cards.into_iter().map(|card| card.copies).sum()
+    Ok(copies)
+}
+
+pub fn try_part1(input: &str) -> Result<Answer, ParseError> {
+    Day4::part1(&Day4::parse(input)?)
+}
+
+pub fn try_part2(input: &str) -> Result<Answer, ParseError> {
+    let answer = Day4::part2(&Day4::parse(input)?)?;
+    Ok(answer.expect("part 2 is solved"))
+}
+
+pub fn part1(input: &str) -> Answer {
+    try_part1(input).unwrap()
+}
+
+pub fn part2(input: &str) -> Answer {
+    try_part2(input).unwrap()
+}
+
+/// Part 1 along with the numbers each card matched
+pub fn explain_part1(input: &str) -> Result<Explanation<CardPoints>, ParseError> {
+    let steps = Day4::parse(input)?
+        .iter()
+        .enumerate()
+        .map(|(idx, card)| {
+            Ok(CardPoints {
+                card: card.id,
+                matching: card
+                    .numbers
+                    .iter()
+                    .copied()
+                    .filter(|number| card.winning_numbers.contains(number))
+                    .collect(),
+                points: card.points().ok_or_else(|| overflow(idx))?,
+            })
+        })
+        .collect::<Result<Vec<CardPoints>, ParseError>>()?;
+
+    let sum = checked_sum(steps.iter().map(|step| &step.points)).map_err(overflow)?;
+    Ok(Explanation::new(sum, steps))
+}
+
+/// Part 2 along with how many copies of each card there ended up being
+pub fn explain_part2(input: &str) -> Result<Explanation<CardCopies>, ParseError> {
+    let cards = Day4::parse(input)?;
+    let copies = copies(&cards)?;
+
+    let steps: Vec<CardCopies> = cards
+        .iter()
+        .zip(copies)
+        .map(|(card, copies)| CardCopies {
+            card: card.id,
+            matches: card.card_winning_nums,
+            copies,
+        })
+        .collect();
+
+    let sum = checked_sum(steps.iter().map(|step| &step.copies)).map_err(overflow)?;
+    Ok(Explanation::new(sum, steps))
+}
+
+/// Every card line that doesn't parse, where parsing stops at the first
+pub fn lint(input: &str) -> Vec<ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| line.parse::<Card>().err().map(|err| err.on_line(idx)))
+        .collect()
+}
+
+/// Part 1 over a pile streamed line by line, for inputs too large to read
+/// into memory
+pub fn part1_reader(reader: impl BufRead) -> Result<Answer, ReadError<ParseError>> {
+    let mut sum = Wide::from(0u32);
+
+    for_each_line(reader, |idx, line| {
+        let card = line.parse::<Card>().map_err(|err| err.on_line(idx))?;
+        let points = card.points().ok_or_else(|| overflow(idx))?;
+        sum = checked_add(&sum, &points).ok_or_else(|| overflow(idx))?;
+        Ok(())
+    })?;
+
+    Ok(sum.into())
+}
+
+/// Part 2 over a pile streamed line by line. Only the copies won for the
+/// cards coming up are kept, so memory is bounded by the most matches on
+/// any one card rather than by the number of cards
+pub fn part2_reader(reader: impl BufRead) -> Result<Answer, ReadError<ParseError>> {
+    // extra copies won for each of the next cards, nearest first
+    let mut won: VecDeque<Wide> = VecDeque::new();
+    let mut total = Wide::from(0u32);
+
+    for_each_line(reader, |idx, line| {
+        let card = line.parse::<Card>().map_err(|err| err.on_line(idx))?;
+        let won_copies = won.pop_front().unwrap_or_default();
+        let copies = checked_add(&won_copies, &Wide::from(1u32)).ok_or_else(|| overflow(idx))?;
+        let num_of_winning_cards = card.card_winning_nums as usize;
+
+        if won.len() < num_of_winning_cards {
+            won.resize(num_of_winning_cards, Wide::from(0u32));
+        }
+
+        for winning_copy in won.iter_mut().take(num_of_winning_cards) {
+            *winning_copy = checked_add(winning_copy, &copies).ok_or_else(|| overflow(idx))?;
+        }
+
+        total = checked_add(&total, &copies).ok_or_else(|| overflow(idx))?;
+        Ok(())
+    })?;
+
+    // copies won past the last card are never played
+    Ok(total.into())
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = include_str!("../input.txt");
+    #[test]
+    fn display_test() {
+        let example = include_str!("../examples/example.txt");
+        let cards = Day4::parse(example).unwrap();
+        let lines: Vec<String> = cards.iter().map(Card::to_string).collect();
+
+        assert_eq!(lines.join("\n"), example);
+        assert_eq!(
+            format!("{:3}", cards[2]),
+            "Card   3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1"
+        );
+    }
 
     #[test]
-    fn part1_test() {
-        let result = part1(INPUT);
-        assert_eq!(result, 13);
+    fn model_test() {
+        let card = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
+            .parse::<Card>()
+            .unwrap();
+
+        assert_eq!(
+            card,
+            Card::new(
+                1,
+                vec![41, 48, 83, 86, 17],
+                vec![83, 86, 6, 31, 17, 9, 48, 53]
+            )
+        );
+        assert_eq!(card.id(), 1);
+        assert_eq!(card.winning_numbers().len(), 5);
+        assert_eq!(card.numbers()[2], 6);
+        assert_eq!(card.matches(), 4);
+        assert_eq!(card.points(), Some(Wide::from(8u32)));
     }
 
     #[test]
-    fn part2_test() {
-        let result = part2(INPUT);
-        assert_eq!(result, 30);
+    fn overflow_test() {
+        // 2^128 points on the second card
+        let numbers: Vec<String> = (1..=129).map(|number| number.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let points = format!("Card 1: 1 | 2\nCard 2: {numbers} | {numbers}");
+        // copies that grow like the fibonacci numbers, past u128 by card 187
+        let copies: Vec<String> = (1..=200)
+            .map(|id| format!("Card {id}: 1 2 | 1 2"))
+            .collect();
+        let copies = copies.join("\n");
+
+        #[cfg(not(feature = "bigint"))]
+        {
+            assert_eq!(
+                try_part1(&points).unwrap_err().to_string(),
+                "2:1: too large to solve without the `bigint` feature"
+            );
+            assert!(matches!(
+                try_part2(&copies),
+                Err(ParseError::Overflow { .. })
+            ));
+            assert!(matches!(
+                part2_reader(copies.as_bytes()),
+                Err(ReadError::Parse(ParseError::Overflow { .. }))
+            ));
+        }
+        #[cfg(feature = "bigint")]
+        {
+            let two = Wide::from(2u32);
+            assert_eq!(try_part1(&points).unwrap(), Answer::from(two.pow(128)));
+            assert_eq!(
+                part2_reader(copies.as_bytes()).unwrap(),
+                try_part2(&copies).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn lint_test() {
+        let input = "Card 1: 41 48 | 83 86\nCard two: 13 32 | 61 30\nCard 3: 41 48 83 86";
+        let errors: Vec<String> = lint(input).iter().map(ParseError::to_string).collect();
+
+        assert_eq!(
+            errors,
+            [
+                "2:6: expected a number, found `two`",
+                "3:20: expected '|' between the winning numbers and the numbers you have",
+            ]
+        );
+        assert_eq!(lint(include_str!("../examples/example.txt")), []);
+    }
+
+    #[test]
+    fn explain_test() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
+                     Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n\
+                     Card 3: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        let explanation = explain_part1(input).unwrap();
+
+        assert_eq!(explanation.answer, Answer::from(10));
+        assert_eq!(
+            explanation.steps[0],
+            CardPoints {
+                card: 1,
+                matching: vec![83, 86, 17, 48],
+                points: Wide::from(8u32),
+            }
+        );
+        assert_eq!(
+            explain_part2(input).unwrap().steps,
+            [
+                CardCopies {
+                    card: 1,
+                    matches: 4,
+                    copies: Wide::from(1u32),
+                },
+                CardCopies {
+                    card: 2,
+                    matches: 2,
+                    copies: Wide::from(2u32),
+                },
+                CardCopies {
+                    card: 3,
+                    matches: 0,
+                    copies: Wide::from(4u32),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let invalid_card_id = "Card 1: 41 48 | 83 86\nCard two: 13 32 | 61 30";
+        let missing_bar = "Card 1: 41 48 83 86";
+
+        assert_eq!(
+            try_part1(invalid_card_id),
+            Err(ParseError::InvalidNumber {
+                position: Position { line: 2, column: 6 },
+                text: "two".to_string(),
+            })
+        );
+        assert_eq!(
+            try_part2(missing_bar).unwrap_err().position(),
+            Position {
+                line: 1,
+                column: 20
+            }
+        );
+    }
+
+    #[test]
+    fn reader_test() {
+        let input = include_str!("../examples/example.txt");
+
+        assert_eq!(part1_reader(input.as_bytes()).unwrap(), part1(input));
+        assert_eq!(part2_reader(input.as_bytes()).unwrap(), part2(input));
+        assert!(matches!(
+            part2_reader("Card 1: 41 48 | 83 86\nCard two: 13 32 | 61 30".as_bytes()),
+            Err(ReadError::Parse(ParseError::InvalidNumber {
+                position: Position { line: 2, column: 6 },
+                ..
+            }))
+        ));
     }
 }
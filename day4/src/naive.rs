@@ -0,0 +1,54 @@
+use common::{Answer, Rng, Solution};
+
+use crate::{generate, Card, Day4, ParseError};
+
+/// Number of the numbers you have that are winning numbers, checking
+/// each against every winning number
+fn naive_matches(card: &Card) -> usize {
+    card.numbers
+        .iter()
+        .filter(|number| {
+            card.winning_numbers
+                .iter()
+                .any(|winning_number| winning_number == *number)
+        })
+        .count()
+}
+
+/// Part 1 by doubling a card's points once for every match after the
+/// first, as a reference for [`crate::try_part1`]
+pub fn naive_part1(input: &str) -> Result<Answer, ParseError> {
+    let mut sum: u64 = 0;
+
+    for card in Day4::parse(input)? {
+        let mut points: u64 = 0;
+        for _ in 0..naive_matches(&card) {
+            points = if points == 0 { 1 } else { points * 2 };
+        }
+        sum += points;
+    }
+
+    Ok(sum.into())
+}
+
+/// Part 2 by scratching every copy of every card one at a time, as a
+/// reference for [`crate::try_part2`]
+pub fn naive_part2(input: &str) -> Result<Answer, ParseError> {
+    let cards = Day4::parse(input)?;
+    let mut queue: Vec<usize> = (0..cards.len()).collect();
+    let mut scratched: u64 = 0;
+
+    while let Some(idx) = queue.pop() {
+        scratched += 1;
+        let last = (idx + naive_matches(&cards[idx])).min(cards.len() - 1);
+        queue.extend(idx + 1..=last);
+    }
+
+    Ok(scratched.into())
+}
+
+/// Input small enough for the naive solvers
+pub fn generate_small(rng: &mut Rng) -> String {
+    let size = 1 + rng.index(12);
+    generate(rng, size)
+}
@@ -0,0 +1,92 @@
+use proptest::prelude::*;
+
+use super::*;
+
+fn format_card(id: usize, winning_numbers: &[u32], numbers: &[u32]) -> String {
+    let join = |numbers: &[u32]| {
+        numbers
+            .iter()
+            .map(|number| format!("{number:>2}"))
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+
+    format!(
+        "Card {id:>3}: {} | {}",
+        join(winning_numbers),
+        join(numbers)
+    )
+}
+
+fn pile() -> impl Strategy<Value = Vec<(Vec<u32>, Vec<u32>)>> {
+    prop::collection::vec(
+        (
+            prop::collection::vec(1..20u32, 1..6),
+            prop::collection::vec(1..20u32, 1..8),
+        ),
+        1..12,
+    )
+}
+
+fn matches(winning_numbers: &[u32], numbers: &[u32]) -> usize {
+    numbers
+        .iter()
+        .filter(|number| winning_numbers.contains(number))
+        .count()
+}
+
+proptest! {
+    #[test]
+    fn parse_never_panics(input in "\\PC*") {
+        let _ = Day4::parse(&input);
+    }
+
+    #[test]
+    fn card_points_double_per_match(
+        winning_numbers in prop::collection::vec(1..100u32, 1..10),
+        numbers in prop::collection::vec(1..100u32, 1..25),
+    ) {
+        let card = format_card(1, &winning_numbers, &numbers).parse::<Card>().unwrap();
+        let expected = match matches(&winning_numbers, &numbers) {
+            0 => 0,
+            matches => 1u32 << (matches - 1),
+        };
+
+        prop_assert_eq!(card.points(), Some(Wide::from(expected)));
+    }
+
+    #[test]
+    fn part2_matches_scratching_every_copy(pile in pile()) {
+        let input = pile
+            .iter()
+            .enumerate()
+            .map(|(idx, (winning_numbers, numbers))| format_card(idx + 1, winning_numbers, numbers))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        // scratch every card one at a time, queueing the copies it wins
+        let mut queue: Vec<usize> = (0..pile.len()).collect();
+        let mut scratched: u32 = 0;
+        while let Some(idx) = queue.pop() {
+            scratched += 1;
+            let (winning_numbers, numbers) = &pile[idx];
+            let last = (idx + matches(winning_numbers, numbers)).min(pile.len() - 1);
+            queue.extend(idx + 1..=last);
+        }
+
+        prop_assert_eq!(part2(&input), scratched);
+        prop_assert_eq!(part2_reader(input.as_bytes()).unwrap(), scratched);
+    }
+
+    #[test]
+    fn display_round_trips(
+        id in 1..1000usize,
+        winning_numbers in prop::collection::vec(1..100u32, 1..10),
+        numbers in prop::collection::vec(1..100u32, 1..25),
+    ) {
+        let line = format_card(id, &winning_numbers, &numbers);
+        let card = line.parse::<Card>().unwrap();
+
+        prop_assert_eq!(format!("{card:3}"), line);
+    }
+}
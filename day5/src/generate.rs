@@ -0,0 +1,70 @@
+use std::fmt::Write;
+
+use common::Rng;
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// Largest number in the almanac, the same scale as the real ones
+const MAX: u64 = 1 << 32;
+
+/// Almanac of `size` seed ranges and seven maps of `size` ranges each.
+/// The source ranges of a map never overlap
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let seeds = (0..size)
+        .map(|_| {
+            let start = rng.range(0..MAX / 2);
+            format!("{start} {}", rng.range(1..MAX / 64))
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    let mut input = format!("seeds: {seeds}\n");
+
+    for categories in CATEGORIES.windows(2) {
+        write!(input, "\n{}-to-{} map:\n", categories[0], categories[1]).unwrap();
+
+        let mut bounds: Vec<u64> = (0..=size).map(|_| rng.range(0..MAX)).collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let mut ranges: Vec<(u64, u64)> = bounds
+            .windows(2)
+            .map(|bound| (bound[0], bound[1] - bound[0]))
+            .collect();
+        if ranges.is_empty() {
+            ranges.push((bounds[0], 1));
+        }
+        rng.shuffle(&mut ranges);
+
+        for (src_start, range) in ranges {
+            let dest_start = rng.range(0..MAX - range);
+            writeln!(input, "{dest_start} {src_start} {range}").unwrap();
+        }
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let input = generate(&mut Rng::new(1), 20);
+
+        assert_eq!(input, generate(&mut Rng::new(1), 20));
+        assert!(crate::try_part1(&input).is_ok());
+        assert!(crate::try_part2(&input).is_ok());
+    }
+}
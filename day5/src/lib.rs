@@ -1,119 +1,379 @@
-use std::{cmp, collections::HashMap, ops::Range, str::FromStr};
+use std::{cmp, error::Error, fmt::Display, ops::Range, str::FromStr};
 
-use regex::Regex;
+use common::{par, Answer, Explanation, Position, Solution, Variant};
 
-struct Almanac {
+mod generate;
+mod naive;
+#[cfg(test)]
+mod proptests;
+
+pub use generate::generate;
+pub use naive::{generate_small, naive_part1, naive_part2};
+
+/// Error from reading an almanac
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Syntax that the almanac is missing
+    Expected {
+        position: Position,
+        expected: &'static str,
+    },
+    /// A seed or range value that isn't a non-negative number
+    InvalidNumber { position: Position, text: String },
+    /// A range whose end is past the largest number the almanac can hold
+    Overflow { position: Position },
+}
+
+impl ParseError {
+    pub fn position(&self) -> Position {
+        match self {
+            ParseError::Expected { position, .. }
+            | ParseError::InvalidNumber { position, .. }
+            | ParseError::Overflow { position } => *position,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Expected { position, expected } => {
+                write!(f, "{position}: expected {expected}")
+            }
+            ParseError::InvalidNumber { position, text } => {
+                write!(f, "{position}: expected a number, found `{text}`")
+            }
+            ParseError::Overflow { position } => {
+                write!(f, "{position}: range ends past {}", i64::MAX)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Error for part 2 when every seed range has a length of zero, put on
+/// the seeds line as the almanac doesn't keep where each range was
+const NO_SEEDS: ParseError = ParseError::Expected {
+    position: Position { line: 1, column: 1 },
+    expected: "a seed range with at least one seed",
+};
+
+/// The seeds to plant and the maps that take them to locations, in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "AlmanacData", into = "AlmanacData")
+)]
+pub struct Almanac {
     seeds: Vec<i64>,
     seed_ranges: Vec<Range<i64>>,
     mappings: Vec<Mapping>,
 }
 
 impl Almanac {
-    fn get_seed_locations(&self) -> Vec<i64> {
-        self.seeds
-            .iter()
-            .map(|seed| {
-                let mut seed_location = *seed;
-                for mapping in &self.mappings {
-                    seed_location = mapping.get_dest_from_src(seed_location);
-                }
-                seed_location
+    /// # Panics
+    ///
+    /// If `seeds` can't be read as start and length pairs, a seed is
+    /// negative, or a seed range ends past `i64::MAX`
+    pub fn new(seeds: Vec<i64>, mappings: Vec<Mapping>) -> Self {
+        Almanac::try_new(seeds, mappings).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_new(seeds: Vec<i64>, mappings: Vec<Mapping>) -> Result<Self, &'static str> {
+        if seeds.is_empty() || !seeds.len().is_multiple_of(2) {
+            return Err("seeds must be start and length pairs");
+        }
+        let seed_ranges = seeds
+            .chunks(2)
+            .map(|range| match range[0].checked_add(range[1]) {
+                Some(end) if range[0] >= 0 && range[1] >= 0 => Ok(range[0]..end),
+                Some(_) => Err("seeds can't be negative"),
+                None => Err("seed range overflows"),
             })
-            .collect()
+            .collect::<Result<_, _>>()?;
+
+        Ok(Almanac {
+            seeds,
+            seed_ranges,
+            mappings,
+        })
     }
 
-    fn get_seed_location_ranges(&mut self) -> Vec<Range<i64>> {
-        for mapping in &self.mappings {
-            let mut seed_location_ranges = vec![];
+    pub fn seeds(&self) -> &[i64] {
+        &self.seeds
+    }
 
-            while !self.seed_ranges.is_empty() {
-                let seed_range = self.seed_ranges.pop().unwrap();
-                let mut range_found = false;
+    /// The seeds read as start and length pairs, for part 2
+    pub fn seed_ranges(&self) -> &[Range<i64>] {
+        &self.seed_ranges
+    }
 
-                for (src_range, dest_range) in &mapping.range_map {
-                    let overlap_start = cmp::max(seed_range.start, src_range.start);
-                    let overlap_end = cmp::min(seed_range.end, src_range.end);
-                    let offset = dest_range.start - src_range.start;
+    pub fn mappings(&self) -> &[Mapping] {
+        &self.mappings
+    }
 
-                    if overlap_start < overlap_end {
-                        seed_location_ranges.push(overlap_start + offset..overlap_end + offset);
+    /// Location of `seed` after going through every map
+    pub fn get_seed_location(&self, seed: i64) -> i64 {
+        let mut seed_location = seed;
+        for mapping in &self.mappings {
+            seed_location = mapping.get_dest_from_src(seed_location);
+        }
+        seed_location
+    }
 
-                        // check for sub-segments that didn't overlap
-                        if overlap_start > seed_range.start {
-                            self.seed_ranges.push(seed_range.start..overlap_start);
-                        }
+    fn get_seed_locations(&self) -> Vec<i64> {
+        par::map(&self.seeds, |seed| self.get_seed_location(*seed))
+    }
 
-                        if seed_range.end > overlap_end {
-                            self.seed_ranges.push(overlap_end..seed_range.end);
-                        }
+    /// Seed ranges with at least one seed in them, as a range of length
+    /// zero has none
+    fn seeded_ranges(&self) -> Result<Vec<Range<i64>>, ParseError> {
+        let seed_ranges: Vec<Range<i64>> = self
+            .seed_ranges
+            .iter()
+            .filter(|range| !range.is_empty())
+            .cloned()
+            .collect();
 
-                        range_found = true;
-                        break;
-                    }
-                }
+        if seed_ranges.is_empty() {
+            return Err(NO_SEEDS);
+        }
+        Ok(seed_ranges)
+    }
 
-                if !range_found {
-                    seed_location_ranges.push(seed_range);
-                }
-            }
+    fn get_seed_location_ranges(&self) -> Result<Vec<Range<i64>>, ParseError> {
+        Ok(self
+            .mappings
+            .iter()
+            .fold(self.seeded_ranges()?, |seed_ranges, mapping| {
+                mapping.get_dest_ranges(seed_ranges)
+            }))
+    }
+}
 
-            self.seed_ranges = seed_location_ranges;
+/// Canonical text of the almanac: the seeds, then each map after a blank
+/// line, every line ending in a newline
+impl Display for Almanac {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "seeds:")?;
+        for seed in &self.seeds {
+            write!(f, " {seed}")?;
         }
+        writeln!(f)?;
 
-        self.seed_ranges.clone()
+        for mapping in &self.mappings {
+            write!(f, "\n{mapping}\n")?;
+        }
+        Ok(())
     }
 }
 
-impl FromStr for Almanac {
-    type Err = ();
+impl Almanac {
+    /// Parse the almanac, pushing every error to `errors` and carrying on
+    /// past it where the rest of the almanac still makes sense
+    fn parse_all(s: &str, errors: &mut Vec<ParseError>) -> Option<Self> {
+        let Some((seeds_line, mappings)) = s.split_once("\n") else {
+            errors.push(ParseError::Expected {
+                position: Position::end_of(s, s),
+                expected: "mappings after the seeds",
+            });
+            return None;
+        };
+
+        let seeds = Almanac::parse_seeds(s, seeds_line, errors);
+        let mut parsed_mappings = vec![];
+        for map_entry in mappings.trim().split("\n\n") {
+            parsed_mappings.push(Mapping::parse_within(s, map_entry, errors));
+        }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (seeds_line, mappings) = s.split_once("\n").unwrap();
-        let (_, seeds_line) = seeds_line.split_once(": ").unwrap();
-        let seeds: Vec<i64> = seeds_line
-            .split_whitespace()
-            .map(|seed| seed.parse::<i64>().unwrap())
+        let (seeds, seed_ranges) = seeds?;
+        Some(Almanac {
+            seeds,
+            seed_ranges,
+            mappings: parsed_mappings
+                .into_iter()
+                .collect::<Option<Vec<Mapping>>>()?,
+        })
+    }
+
+    fn parse_seeds(
+        s: &str,
+        seeds_line: &str,
+        errors: &mut Vec<ParseError>,
+    ) -> Option<(Vec<i64>, Vec<Range<i64>>)> {
+        let Some((_, seeds_line)) = seeds_line.split_once(": ") else {
+            errors.push(ParseError::Expected {
+                position: Position::within(s, seeds_line),
+                expected: "'seeds: '",
+            });
+            return None;
+        };
+        let seed_numbers: Vec<&str> = seeds_line.split_whitespace().collect();
+        let seeds: Vec<Option<i64>> = seed_numbers
+            .iter()
+            .map(|seed| parse_number(s, seed).map_err(|err| errors.push(err)).ok())
             .collect();
 
-        let re = Regex::new(r"[0-9]+\s[0-9]+").unwrap();
-        let matches: Vec<&str> = re.find_iter(seeds_line).map(|mat| mat.as_str()).collect();
+        if seeds.is_empty() || !seeds.len().is_multiple_of(2) {
+            errors.push(ParseError::Expected {
+                position: Position::end_of(s, seeds_line),
+                expected: "seeds in start and length pairs",
+            });
+            return None;
+        }
 
-        let seed_ranges: Vec<Range<i64>> = matches
-            .into_iter()
-            .map(|range| {
-                let (start, len) = range.split_once(' ').unwrap();
-                let start = start.parse::<i64>().unwrap();
-                let len = len.parse::<i64>().unwrap();
-                start..start + len
+        let seed_ranges: Vec<Option<Range<i64>>> = seeds
+            .chunks(2)
+            .zip(seed_numbers.chunks(2))
+            .map(|(range, numbers)| {
+                let (start, length) = (range[0]?, range[1]?);
+                range_end(s, start, length, numbers[1])
+                    .map(|end| start..end)
+                    .map_err(|err| errors.push(err))
+                    .ok()
             })
             .collect();
 
-        let mappings: Vec<Mapping> = mappings
-            .trim()
-            .split("\n\n")
-            .map(|map_entry| map_entry.parse::<Mapping>().unwrap())
-            .collect();
+        Some((
+            seeds.into_iter().collect::<Option<Vec<i64>>>()?,
+            seed_ranges
+                .into_iter()
+                .collect::<Option<Vec<Range<i64>>>>()?,
+        ))
+    }
+}
 
-        Ok(Almanac {
-            seeds,
-            seed_ranges,
-            mappings,
-        })
+impl FromStr for Almanac {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut errors = vec![];
+        let almanac = Almanac::parse_all(s, &mut errors);
+        first_error(almanac, errors)
     }
 }
 
-#[derive(Debug)]
-struct Mapping {
+/// The parsed value, or the first error from parsing that collects them
+/// all
+fn first_error<T>(parsed: Option<T>, errors: Vec<ParseError>) -> Result<T, ParseError> {
+    match (parsed, errors.into_iter().next()) {
+        (Some(parsed), None) => Ok(parsed),
+        (_, Some(err)) => Err(err),
+        (None, None) => unreachable!("parsing failed without an error"),
+    }
+}
+
+/// Source and destination range of a map row
+fn parse_range(text: &str, row: &str) -> Result<(Range<i64>, Range<i64>), ParseError> {
+    let mapping: Vec<&str> = row.split_whitespace().collect();
+    let [dest_start, src_start, range] = mapping[..] else {
+        return Err(ParseError::Expected {
+            position: Position::within(text, row),
+            expected: "destination start, source start and range length",
+        });
+    };
+    let dest_start = parse_number(text, dest_start)?;
+    let src_start = parse_number(text, src_start)?;
+    let length = parse_number(text, range)?;
+
+    let dest_end = range_end(text, dest_start, length, range)?;
+    let src_end = range_end(text, src_start, length, range)?;
+
+    // end in range is exclusive (start <= x < end)
+    Ok((src_start..src_end, dest_start..dest_end))
+}
+
+fn parse_number(text: &str, number: &str) -> Result<i64, ParseError> {
+    number
+        .parse::<i64>()
+        .ok()
+        .filter(|number| *number >= 0)
+        .ok_or_else(|| ParseError::InvalidNumber {
+            position: Position::within(text, number),
+            text: number.to_string(),
+        })
+}
+
+/// End of the range of `length` from `start`, reporting overflow at
+/// `at`, the length's text within `text`
+fn range_end(text: &str, start: i64, length: i64, at: &str) -> Result<i64, ParseError> {
+    start
+        .checked_add(length)
+        .ok_or_else(|| ParseError::Overflow {
+            position: Position::within(text, at),
+        })
+}
+
+/// Map from one category to the next, like `seed-to-soil`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "MappingData", into = "MappingData")
+)]
+pub struct Mapping {
     src: String,
     dest: String,
-    range_map: HashMap<Range<i64>, Range<i64>>,
+    /// Source and destination ranges in almanac order
+    range_map: Vec<(Range<i64>, Range<i64>)>,
 }
 
 impl Mapping {
-    fn get_dest_from_src(&self, src: i64) -> i64 {
-        for src_range in self.range_map.keys() {
+    /// Map from `src` to `dest` with `ranges` of destination start, source
+    /// start and length, as in the almanac
+    ///
+    /// # Panics
+    ///
+    /// If a range has a negative number or ends past `i64::MAX`
+    pub fn new(src: &str, dest: &str, ranges: impl IntoIterator<Item = (i64, i64, i64)>) -> Self {
+        Mapping::try_new(src, dest, ranges).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_new(
+        src: &str,
+        dest: &str,
+        ranges: impl IntoIterator<Item = (i64, i64, i64)>,
+    ) -> Result<Self, &'static str> {
+        let range_map = ranges
+            .into_iter()
+            .map(|(dest_start, src_start, length)| {
+                if dest_start < 0 || src_start < 0 || length < 0 {
+                    return Err("ranges can't be negative");
+                }
+                let end = |start: i64| start.checked_add(length).ok_or("range overflows");
+                Ok((src_start..end(src_start)?, dest_start..end(dest_start)?))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Mapping {
+            src: src.to_string(),
+            dest: dest.to_string(),
+            range_map,
+        })
+    }
+
+    /// Category mapped from
+    pub fn src(&self) -> &str {
+        &self.src
+    }
+
+    /// Category mapped to
+    pub fn dest(&self) -> &str {
+        &self.dest
+    }
+
+    /// Source and destination ranges, in the order of the almanac
+    pub fn ranges(&self) -> &[(Range<i64>, Range<i64>)] {
+        &self.range_map
+    }
+
+    /// Destination of `src`, which is `src` itself when no range covers
+    /// it. When ranges overlap the first one in the almanac wins
+    pub fn get_dest_from_src(&self, src: i64) -> i64 {
+        for (src_range, dest_range) in &self.range_map {
             if src_range.contains(&src) {
-                let dest_range = self.range_map.get(src_range).unwrap();
                 let offset = dest_range.start - src_range.start;
                 return src + offset;
             }
@@ -121,10 +381,114 @@ impl Mapping {
 
         src
     }
+
+    /// Ranges that `seed_ranges` cover once mapped, split wherever they
+    /// cross the edge of one of the map's ranges
+    fn get_dest_ranges(&self, mut seed_ranges: Vec<Range<i64>>) -> Vec<Range<i64>> {
+        let mut seed_location_ranges = vec![];
+
+        while let Some(seed_range) = seed_ranges.pop() {
+            let mut range_found = false;
+
+            for (src_range, dest_range) in &self.range_map {
+                let overlap_start = cmp::max(seed_range.start, src_range.start);
+                let overlap_end = cmp::min(seed_range.end, src_range.end);
+                let offset = dest_range.start - src_range.start;
+
+                if overlap_start < overlap_end {
+                    seed_location_ranges.push(overlap_start + offset..overlap_end + offset);
+
+                    // check for sub-segments that didn't overlap
+                    if overlap_start > seed_range.start {
+                        seed_ranges.push(seed_range.start..overlap_start);
+                    }
+
+                    if seed_range.end > overlap_end {
+                        seed_ranges.push(overlap_end..seed_range.end);
+                    }
+
+                    range_found = true;
+                    break;
+                }
+            }
+
+            if !range_found {
+                seed_location_ranges.push(seed_range);
+            }
+        }
+
+        seed_location_ranges
+    }
+}
+
+impl Mapping {
+    /// Parse a mapping that is part of the almanac `text`, so errors
+    /// point into the whole almanac. Every error is pushed to `errors`,
+    /// with parsing carrying on to the next range after a bad one
+    fn parse_within(text: &str, s: &str, errors: &mut Vec<ParseError>) -> Option<Self> {
+        let header = Mapping::parse_header(text, s).map_err(|err| errors.push(err));
+        let (src, dest, mappings) = header.ok()?;
+
+        let mut range_map = vec![];
+        let mut valid = true;
+
+        for row in mappings.split("\n") {
+            match parse_range(text, row) {
+                Ok(range) => range_map.push(range),
+                Err(err) => {
+                    errors.push(err);
+                    valid = false;
+                }
+            }
+        }
+
+        valid.then(|| Mapping {
+            src: src.to_string(),
+            dest: dest.to_string(),
+            range_map,
+        })
+    }
+
+    /// Source and destination categories of a map, and the text of its
+    /// ranges
+    fn parse_header<'a>(text: &str, s: &'a str) -> Result<(&'a str, &'a str, &'a str), ParseError> {
+        let (src_dest, mappings) = s.split_once("\n").ok_or_else(|| ParseError::Expected {
+            position: Position::end_of(text, s),
+            expected: "ranges after the map header",
+        })?;
+
+        let (src_dest, _) = src_dest
+            .split_once(' ')
+            .ok_or_else(|| ParseError::Expected {
+                position: Position::within(text, src_dest),
+                expected: "' map:' after the categories",
+            })?;
+        let (src, dest) = src_dest
+            .split_once("-to-")
+            .ok_or_else(|| ParseError::Expected {
+                position: Position::within(text, src_dest),
+                expected: "'-to-' between the categories",
+            })?;
+
+        Ok((src, dest, mappings))
+    }
+}
+
+/// Canonical text of a map: its header, then a line of destination start,
+/// source start and length for each range in almanac order. There's no
+/// newline after the last range
+impl Display for Mapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-to-{} map:", self.src, self.dest)?;
+        for (src, dest) in &self.range_map {
+            write!(f, "\n{} {} {}", dest.start, src.start, src.end - src.start)?;
+        }
+        Ok(())
+    }
 }
 
 impl FromStr for Mapping {
-    type Err = ();
+    type Err = ParseError;
 
     /// Takes a mapping string and converts it to the Mapping struct
     /// Example:
@@ -132,63 +496,277 @@ impl FromStr for Mapping {
     /// 50 98 2
     /// 52 50 48
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (src_dest, mappings) = s.split_once("\n").unwrap();
+        let mut errors = vec![];
+        let mapping = Mapping::parse_within(s, s, &mut errors);
+        first_error(mapping, errors)
+    }
+}
+
+/// An almanac as it's serialized, with the seed ranges spelled out
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct AlmanacData {
+    seeds: Vec<i64>,
+    /// Checked against the seeds when present, as it's worked out from them
+    #[serde(default)]
+    seed_ranges: Option<Vec<Range<i64>>>,
+    mappings: Vec<Mapping>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AlmanacData> for Almanac {
+    type Error = &'static str;
+
+    fn try_from(data: AlmanacData) -> Result<Self, Self::Error> {
+        let almanac = Almanac::try_new(data.seeds, data.mappings)?;
+        match data.seed_ranges {
+            Some(seed_ranges) if seed_ranges != almanac.seed_ranges => {
+                Err("seed ranges don't match the seeds")
+            }
+            _ => Ok(almanac),
+        }
+    }
+}
 
-        let (src_dest, _) = src_dest.split_once(' ').unwrap();
-        let (src, dest) = src_dest.split_once("-to-").unwrap();
+#[cfg(feature = "serde")]
+impl From<Almanac> for AlmanacData {
+    fn from(almanac: Almanac) -> Self {
+        AlmanacData {
+            seeds: almanac.seeds,
+            seed_ranges: Some(almanac.seed_ranges),
+            mappings: almanac.mappings,
+        }
+    }
+}
 
-        let mappings: Vec<&str> = mappings.split("\n").collect();
-        let mut range_map = HashMap::new();
+/// A mapping as it's serialized, with its ranges as in the almanac
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct MappingData {
+    src: String,
+    dest: String,
+    ranges: Vec<RangeData>,
+}
 
-        for mapping in mappings {
-            let mapping: Vec<&str> = mapping.splitn(3, ' ').collect();
-            let dest_start = mapping[0].parse::<i64>().unwrap();
-            let src_start = mapping[1].parse::<i64>().unwrap();
-            let range = mapping[2].parse::<i64>().unwrap();
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RangeData {
+    dest_start: i64,
+    src_start: i64,
+    length: i64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MappingData> for Mapping {
+    type Error = &'static str;
 
-            let dest_end = dest_start + range;
-            let src_end = src_start + range;
+    fn try_from(data: MappingData) -> Result<Self, Self::Error> {
+        let ranges = data
+            .ranges
+            .iter()
+            .map(|range| (range.dest_start, range.src_start, range.length));
+        Mapping::try_new(&data.src, &data.dest, ranges)
+    }
+}
 
-            // end in range is exclusive (start <= x < end)
-            range_map.insert(src_start..src_end, dest_start..dest_end);
+#[cfg(feature = "serde")]
+impl From<Mapping> for MappingData {
+    fn from(mapping: Mapping) -> Self {
+        MappingData {
+            ranges: mapping
+                .range_map
+                .into_iter()
+                .map(|(src, dest)| RangeData {
+                    dest_start: dest.start,
+                    src_start: src.start,
+                    length: src.end - src.start,
+                })
+                .collect(),
+            src: mapping.src,
+            dest: mapping.dest,
         }
+    }
+}
 
-        Ok(Mapping {
-            src: src.to_string(),
-            dest: dest.to_string(),
-            range_map,
-        })
+/// Value of a seed at one stage of the almanac, as a step of explaining
+/// part 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeedStage {
+    pub seed: i64,
+    pub category: String,
+    pub value: i64,
+}
+
+/// Ranges the seed ranges cover at one stage of the almanac and the
+/// lowest value in them, as a step of explaining part 2
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangeStage {
+    pub category: String,
+    pub ranges: usize,
+    pub lowest: i64,
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Almanac;
+
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        input.parse::<Almanac>()
+    }
+
+    // dest range start, src range start, range length
+    // upper num = (range start - 1) + range length
+    fn part1(almanac: &Self::Input) -> Result<Answer, Self::Error> {
+        let locations = almanac.get_seed_locations();
+        // the almanac always has at least one seed
+        Ok((*locations.iter().min().unwrap()).into())
+    }
+
+    fn part2(almanac: &Self::Input) -> Result<Option<Answer>, Self::Error> {
+        let mut location_ranges = almanac.get_seed_location_ranges()?;
+        location_ranges.sort_by_key(|range| range.start);
+        Ok(Some(location_ranges[0].start.into()))
     }
 }
 
-// dest range start, src range start, range length
-// upper num = (range start - 1) + range length
-pub fn part1(input: &str) -> i64 {
-    let almanac = input.parse::<Almanac>().unwrap();
+pub fn try_part1(input: &str) -> Result<Answer, ParseError> {
+    Day5::part1(&Day5::parse(input)?)
+}
+
+pub fn try_part2(input: &str) -> Result<Answer, ParseError> {
+    let answer = Day5::part2(&Day5::parse(input)?)?;
+    Ok(answer.expect("part 2 is solved"))
+}
+
+pub fn part1(input: &str) -> Answer {
+    try_part1(input).unwrap()
+}
+
+pub fn part2(input: &str) -> Answer {
+    try_part2(input).unwrap()
+}
+
+/// Ways of solving each part, for timing against each other
+pub const VARIANTS: &[Variant<ParseError>] = &[
+    Variant::new("intervals", 2, try_part2),
+    Variant::new("per-seed", 2, naive_part2).unless(naive::too_many_seeds),
+];
+
+/// Part 1 along with the value of each seed at every stage, from the
+/// seed itself to its location
+pub fn explain_part1(input: &str) -> Result<Explanation<SeedStage>, ParseError> {
+    let almanac = Day5::parse(input)?;
+    let first = almanac
+        .mappings
+        .first()
+        .map_or("seed", |mapping| mapping.src());
+    let mut steps = vec![];
+
+    for &seed in &almanac.seeds {
+        let mut value = seed;
+        steps.push(SeedStage {
+            seed,
+            category: first.to_string(),
+            value,
+        });
+
+        for mapping in &almanac.mappings {
+            value = mapping.get_dest_from_src(value);
+            steps.push(SeedStage {
+                seed,
+                category: mapping.dest().to_string(),
+                value,
+            });
+        }
+    }
+
     let locations = almanac.get_seed_locations();
-    *locations.iter().min().unwrap()
+    // the almanac always has at least one seed
+    Ok(Explanation::new(*locations.iter().min().unwrap(), steps))
 }
 
-pub fn part2(input: &str) -> i64 {
-    let mut almanac = input.parse::<Almanac>().unwrap();
-    let mut location_ranges = almanac.get_seed_location_ranges();
-    location_ranges.sort_by(|a, b| a.start.cmp(&b.start));
-    location_ranges[0].start
+/// Part 2 along with how the seed ranges split up at every stage and the
+/// lowest value they reach there
+pub fn explain_part2(input: &str) -> Result<Explanation<RangeStage>, ParseError> {
+    let almanac = Day5::parse(input)?;
+    let first = almanac
+        .mappings
+        .first()
+        .map_or("seed", |mapping| mapping.src());
+
+    let stage = |category: &str, ranges: &[Range<i64>]| RangeStage {
+        category: category.to_string(),
+        ranges: ranges.len(),
+        // there's always at least one seeded range
+        lowest: ranges.iter().map(|range| range.start).min().unwrap(),
+    };
+
+    let mut ranges = almanac.seeded_ranges()?;
+    let mut steps = vec![stage(first, &ranges)];
+    for mapping in &almanac.mappings {
+        ranges = mapping.get_dest_ranges(ranges);
+        steps.push(stage(mapping.dest(), &ranges));
+    }
+
+    // the last stage holds the lowest location
+    let lowest = steps[steps.len() - 1].lowest;
+    Ok(Explanation::new(lowest, steps))
+}
+
+/// Every error in the almanac, where parsing stops at the first
+pub fn lint(input: &str) -> Vec<ParseError> {
+    let mut errors = vec![];
+    Almanac::parse_all(input, &mut errors);
+    errors
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = include_str!("../input.txt");
-    const MAPPING_TEST_INPUT: &'static str = include_str!("../mapping_test_input.txt");
+    const MAPPING_TEST_INPUT: &str = include_str!("../mapping_test_input.txt");
+
+    #[test]
+    fn display_test() {
+        let input = format!("{}\n", include_str!("../examples/example.txt"));
+
+        assert_eq!(Day5::parse(&input).unwrap().to_string(), input);
+        assert_eq!(
+            MAPPING_TEST_INPUT.parse::<Mapping>().unwrap().to_string(),
+            MAPPING_TEST_INPUT
+        );
+    }
 
     #[test]
     fn mapping_from_str_test() {
         let mapping = MAPPING_TEST_INPUT.parse::<Mapping>().unwrap();
-        assert_eq!(String::from("seed"), mapping.src);
-        assert_eq!(String::from("soil"), mapping.dest);
-        assert_eq!((50..52), *mapping.range_map.get(&(98..100)).unwrap());
+        assert_eq!("seed", mapping.src());
+        assert_eq!("soil", mapping.dest());
+        assert_eq!(mapping.ranges()[0], (98..100, 50..52));
+    }
+
+    #[test]
+    fn model_test() {
+        let mapping = Mapping::new("seed", "soil", [(50, 98, 2), (52, 50, 48)]);
+        assert_eq!(mapping, MAPPING_TEST_INPUT.parse::<Mapping>().unwrap());
+        assert_eq!(mapping.ranges(), [(98..100, 50..52), (50..98, 52..100)]);
+
+        let almanac = Almanac::new(vec![79, 14, 55, 13], vec![mapping]);
+        assert_eq!(almanac.seed_ranges(), [79..93, 55..68]);
+        assert_eq!(almanac.mappings().len(), 1);
+        assert_eq!(almanac.get_seed_location(98), 50);
     }
 
     #[test]
@@ -204,14 +782,101 @@ mod tests {
     }
 
     #[test]
-    fn part1_test() {
-        let result = part1(INPUT);
-        assert_eq!(result, 35);
+    fn explain_test() {
+        let input = include_str!("../examples/example.txt");
+        let explanation = explain_part1(input).unwrap();
+        let seed_79: Vec<(&str, i64)> = explanation
+            .steps
+            .iter()
+            .filter(|step| step.seed == 79)
+            .map(|step| (step.category.as_str(), step.value))
+            .collect();
+
+        assert_eq!(explanation.answer, Answer::from(35));
+        assert_eq!(
+            seed_79,
+            [
+                ("seed", 79),
+                ("soil", 81),
+                ("fertilizer", 81),
+                ("water", 81),
+                ("light", 74),
+                ("temperature", 78),
+                ("humidity", 78),
+                ("location", 82),
+            ]
+        );
+
+        let explanation = explain_part2(input).unwrap();
+        assert_eq!(explanation.answer, Answer::from(46));
+        assert_eq!(
+            explanation.steps[0],
+            RangeStage {
+                category: "seed".to_string(),
+                ranges: 2,
+                lowest: 55,
+            }
+        );
+        assert_eq!(explanation.steps.len(), 8);
     }
 
     #[test]
-    fn part2_test() {
-        let result = part2(INPUT);
-        assert_eq!(result, 46);
+    fn lint_test() {
+        let input =
+            "seeds: 79 x 55\n\nseed-to-soil map:\n50 98 2\n52 fifty 48\n\nsoil fertilizer\n0 15";
+        let positions: Vec<Position> = lint(input).iter().map(ParseError::position).collect();
+
+        assert_eq!(
+            positions,
+            [
+                Position {
+                    line: 1,
+                    column: 11
+                },
+                Position {
+                    line: 1,
+                    column: 15
+                },
+                Position { line: 5, column: 4 },
+                Position { line: 7, column: 1 },
+            ]
+        );
+        assert_eq!(lint(include_str!("../examples/example.txt")), []);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let invalid_range = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 fifty 48";
+        let short_range = "seeds: 79 14\n\nseed-to-soil map:\n50 98";
+        let odd_seeds = "seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2";
+        let overflow = "seeds: 79 14\n\nseed-to-soil map:\n9223372036854775807 98 2";
+
+        assert_eq!(
+            try_part1(invalid_range),
+            Err(ParseError::InvalidNumber {
+                position: Position { line: 5, column: 4 },
+                text: "fifty".to_string(),
+            })
+        );
+        assert_eq!(
+            try_part1(short_range).unwrap_err().to_string(),
+            "4:1: expected destination start, source start and range length"
+        );
+        assert_eq!(
+            try_part1(overflow),
+            Err(ParseError::Overflow {
+                position: Position {
+                    line: 4,
+                    column: 24
+                }
+            })
+        );
+        assert_eq!(
+            try_part2(odd_seeds).unwrap_err().position(),
+            Position {
+                line: 1,
+                column: 16
+            }
+        );
     }
 }
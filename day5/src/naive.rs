@@ -0,0 +1,107 @@
+use std::fmt::Write;
+
+use common::{Answer, Rng, Solution};
+
+use crate::{Almanac, Day5, ParseError, NO_SEEDS};
+
+/// Location of `seed`, looking through every row of each map for the
+/// first that covers it
+fn naive_location(almanac: &Almanac, seed: i64) -> i64 {
+    almanac.mappings.iter().fold(seed, |src, mapping| {
+        mapping
+            .ranges()
+            .iter()
+            .find(|(src_range, _)| src_range.start <= src && src < src_range.end)
+            .map_or(src, |(src_range, dest_range)| {
+                dest_range.start + (src - src_range.start)
+            })
+    })
+}
+
+/// Part 1 by following each seed through every map, as a reference for
+/// [`crate::try_part1`]
+pub fn naive_part1(input: &str) -> Result<Answer, ParseError> {
+    let almanac = Day5::parse(input)?;
+    let lowest = almanac
+        .seeds
+        .iter()
+        .map(|&seed| naive_location(&almanac, seed))
+        .min();
+
+    // the almanac always has at least one seed
+    Ok(lowest.unwrap().into())
+}
+
+/// Part 2 by following every seed of every range through every map, as
+/// a reference for [`crate::try_part2`]. Only for small almanacs
+pub fn naive_part2(input: &str) -> Result<Answer, ParseError> {
+    let almanac = Day5::parse(input)?;
+    let lowest = almanac
+        .seed_ranges
+        .iter()
+        .flat_map(|range| range.clone())
+        .map(|seed| naive_location(&almanac, seed))
+        .min();
+
+    Ok(lowest.ok_or(NO_SEEDS)?.into())
+}
+
+/// Most seeds [`naive_part2`] is run on by `aoc compare`, as a real
+/// almanac has billions
+const MAX_SEEDS: u64 = 1_000_000;
+
+/// Why [`naive_part2`] would take too long on `input`, when the seed
+/// ranges hold more than [`MAX_SEEDS`] seeds between them
+pub(crate) fn too_many_seeds(input: &str) -> Option<String> {
+    let almanac = Day5::parse(input).ok()?;
+    // a few ranges of billions of seeds each can add up past a `u64`
+    let seeds = almanac.seed_ranges.iter().fold(0u64, |seeds, range| {
+        seeds.saturating_add(range.end.saturating_sub(range.start).max(0) as u64)
+    });
+
+    (seeds > MAX_SEEDS).then(|| format!("over {MAX_SEEDS} seeds is too many to try one at a time"))
+}
+
+/// Almanac small enough for the naive solvers, with every number below
+/// 100 and maps whose rows may overlap
+pub fn generate_small(rng: &mut Rng) -> String {
+    let seeds = (0..rng.range(1..4))
+        .map(|_| format!("{} {}", rng.range(0..100), rng.range(0..20)))
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    let mut input = format!("seeds: {seeds}\n");
+
+    for map in 0..rng.range(1..4) {
+        write!(input, "\nc{map}-to-c{} map:\n", map + 1).unwrap();
+        for _ in 0..rng.range(1..5) {
+            let (dest_start, src_start) = (rng.range(0..100), rng.range(0..100));
+            writeln!(input, "{dest_start} {src_start} {}", rng.range(0..20)).unwrap();
+        }
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_many_seeds_test() {
+        assert_eq!(
+            too_many_seeds(include_str!("../examples/example.txt")),
+            None
+        );
+        assert_eq!(
+            too_many_seeds("seeds: 79 14 55 1000000\n\nseed-to-soil map:\n50 98 2"),
+            Some("over 1000000 seeds is too many to try one at a time".to_string())
+        );
+        let huge = "seeds: 0 9223372036854775807 1 9223372036854775806 2 9223372036854775805";
+        assert_eq!(
+            too_many_seeds(&format!("{huge}\n\nseed-to-soil map:\n50 98 2")),
+            Some("over 1000000 seeds is too many to try one at a time".to_string())
+        );
+        assert_eq!(too_many_seeds("seeds:"), None);
+    }
+}
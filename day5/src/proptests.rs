@@ -0,0 +1,99 @@
+use proptest::prelude::*;
+
+use super::*;
+
+/// Destination start, source start and range length of a map row
+type Row = (i64, i64, i64);
+
+/// Map rows whose source ranges never overlap
+fn rows() -> impl Strategy<Value = Vec<Row>> {
+    prop::collection::vec((0..200i64, 1..20i64, 0..200i64), 1..8).prop_map(|rows| {
+        let mut src_start = 0;
+        rows.into_iter()
+            .map(|(gap, range, dest_start)| {
+                src_start += gap;
+                let row = (dest_start, src_start, range);
+                src_start += range;
+                row
+            })
+            .collect()
+    })
+}
+
+fn format_mapping(name: &str, rows: &[Row]) -> String {
+    let rows = rows
+        .iter()
+        .map(|(dest_start, src_start, range)| format!("{dest_start} {src_start} {range}"))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!("{name} map:\n{rows}")
+}
+
+fn format_almanac(seeds: &[(i64, i64)], mappings: &[Vec<Row>]) -> String {
+    let seeds = seeds
+        .iter()
+        .map(|(start, range)| format!("{start} {range}"))
+        .collect::<Vec<String>>()
+        .join(" ");
+    let mappings = mappings
+        .iter()
+        .enumerate()
+        .map(|(idx, rows)| format_mapping(&format!("c{idx}-to-c{}", idx + 1), rows))
+        .collect::<Vec<String>>()
+        .join("\n\n");
+
+    format!("seeds: {seeds}\n\n{mappings}")
+}
+
+fn naive_dest(rows: &[Row], src: i64) -> i64 {
+    rows.iter()
+        .find(|&&(_, src_start, range)| src_start <= src && src < src_start + range)
+        .map_or(src, |&(dest_start, src_start, _)| {
+            dest_start + src - src_start
+        })
+}
+
+fn almanac() -> impl Strategy<Value = (Vec<(i64, i64)>, Vec<Vec<Row>>)> {
+    (
+        prop::collection::vec((0..300i64, 1..50i64), 1..5),
+        prop::collection::vec(rows(), 1..5),
+    )
+}
+
+proptest! {
+    #[test]
+    fn parse_never_panics(input in "\\PC*") {
+        let _ = Day5::parse(&input);
+    }
+
+    #[test]
+    fn get_dest_from_src_matches_naive_scan(rows in rows(), src in 0..400i64) {
+        let mapping = format_mapping("seed-to-soil", &rows).parse::<Mapping>().unwrap();
+
+        prop_assert_eq!(mapping.get_dest_from_src(src), naive_dest(&rows, src));
+    }
+
+    #[test]
+    fn range_splitting_keeps_every_seed((seeds, mappings) in almanac()) {
+        let almanac = format_almanac(&seeds, &mappings).parse::<Almanac>().unwrap();
+        let location_ranges = almanac.get_seed_location_ranges().unwrap();
+
+        let seed_count: i64 = seeds.iter().map(|(_, range)| range).sum();
+        let location_count: i64 = location_ranges.iter().map(|range| range.end - range.start).sum();
+        prop_assert_eq!(location_count, seed_count);
+
+        // every seed ends up in one of the location ranges
+        for seed in seeds.iter().flat_map(|&(start, range)| start..start + range) {
+            let location = mappings.iter().fold(seed, |src, rows| naive_dest(rows, src));
+            prop_assert!(location_ranges.iter().any(|range| range.contains(&location)));
+        }
+    }
+
+    #[test]
+    fn display_round_trips((seeds, mappings) in almanac()) {
+        let input = format_almanac(&seeds, &mappings) + "\n";
+
+        prop_assert_eq!(input.parse::<Almanac>().unwrap().to_string(), input);
+    }
+}
@@ -0,0 +1,40 @@
+use common::Rng;
+
+/// Race sheet of `size` races lasting up to 99 milliseconds, each with a
+/// record that can be beaten. Part 2 reads the whole sheet as one race,
+/// so every extra race makes it about a hundred times longer, and past
+/// ten or so races it needs the `bigint` feature
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let races: Vec<(u64, u64)> = (0..size.max(1))
+        .map(|_| {
+            let time = rng.range(7..100);
+            (time, rng.range(1..time * time / 4))
+        })
+        .collect();
+
+    let (times, distances): (Vec<String>, Vec<String>) = races
+        .iter()
+        .map(|(time, distance)| (format!("{time:>5}"), format!("{distance:>5}")))
+        .unzip();
+
+    format!(
+        "Time:    {}\nDistance:{}\n",
+        times.join(" "),
+        distances.join(" ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let input = generate(&mut Rng::new(1), 3);
+
+        assert_eq!(input, generate(&mut Rng::new(1), 3));
+        assert_eq!(input.lines().count(), 2);
+        assert!(crate::try_part1(&input).is_ok());
+        assert!(crate::try_part2(&input).is_ok());
+    }
+}
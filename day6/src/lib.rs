@@ -1,82 +1,445 @@
-pub fn part1(input: &str) -> u32 {
-    let (time, distance) = input.split_once('\n').unwrap();
-    let (_, time) = time.split_once("Time:").unwrap();
-    let time: Vec<&str> = time.split_ascii_whitespace().collect();
-    let (_, distance) = distance.split_once("Distance:").unwrap();
-    let distance: Vec<&str> = distance.split_ascii_whitespace().collect();
-
-    let time_distance_iter = time.iter().zip(distance.iter());
-
-    let mut ways_to_win_vec = vec![];
-
-    for (_, (time, distance)) in time_distance_iter.enumerate() {
-        println!("Time: {} Distance: {}", time, distance);
-        let time = time.parse::<u32>().unwrap();
-        let distance = distance.parse::<u32>().unwrap();
-        let mut ways_to_win: u32 = 0;
-
-        for speed in 1..time {
-            let time_moving = time - speed;
-            let dist = time_moving * speed;
-            if dist > distance {
-                ways_to_win += 1;
+use std::{error::Error, fmt::Display};
+
+use common::{
+    checked_mul, checked_product, sqrt, Answer, Explanation, Position, Solution, Variant, Wide,
+};
+use log::debug;
+
+mod generate;
+mod naive;
+#[cfg(test)]
+mod proptests;
+
+pub use generate::generate;
+pub use naive::{generate_small, naive_part1, naive_part2};
+
+/// Error from reading the race sheet, or from solving it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Syntax that the race sheet is missing
+    Expected {
+        position: Position,
+        expected: &'static str,
+    },
+    /// A time or distance that isn't a number
+    InvalidNumber { position: Position, text: String },
+    /// A time or distance too long for [`Wide`] without the `bigint`
+    /// feature
+    Overflow { position: Position },
+    /// A race, or the answer, too large to solve in [`Wide`] without the
+    /// `bigint` feature
+    TooLarge,
+}
+
+impl ParseError {
+    /// Where in the race sheet the error is, `None` for one from solving
+    /// it
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            ParseError::Expected { position, .. }
+            | ParseError::InvalidNumber { position, .. }
+            | ParseError::Overflow { position } => Some(*position),
+            ParseError::TooLarge => None,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Expected { position, expected } => {
+                write!(f, "{position}: expected {expected}")
+            }
+            ParseError::InvalidNumber { position, text } => {
+                write!(f, "{position}: expected a number, found `{text}`")
             }
+            ParseError::Overflow { position } => {
+                write!(
+                    f,
+                    "{position}: too large to solve without the `bigint` feature"
+                )
+            }
+            ParseError::TooLarge => write!(f, "too large to solve without the `bigint` feature"),
         }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Race times and record distances, read both as separate races and,
+/// ignoring the spaces between them, as one long race
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RaceSheet {
+    races: Vec<(Wide, Wide)>,
+    long_race: (Wide, Wide),
+}
 
-        ways_to_win_vec.push(ways_to_win);
+impl RaceSheet {
+    /// Time and record distance of each race, in order
+    pub fn races(&self) -> &[(Wide, Wide)] {
+        &self.races
     }
 
-    ways_to_win_vec.into_iter().product::<u32>()
+    /// Time and record distance of the one long race, for part 2
+    pub fn long_race(&self) -> (&Wide, &Wide) {
+        (&self.long_race.0, &self.long_race.1)
+    }
 }
 
-pub fn part2(input: &str) -> u64 {
-    let (time, distance) = input.split_once('\n').unwrap();
-    let (_, time) = time.split_once("Time:").unwrap();
-    let time: Vec<&str> = time.split_ascii_whitespace().collect();
-    let (_, distance) = distance.split_once("Distance:").unwrap();
-    let distance: Vec<&str> = distance.split_ascii_whitespace().collect();
+/// Parse `number`, reporting errors at `at`, a subslice of `input`. Digits
+/// too many for [`Wide`] are an overflow rather than an invalid number
+fn parse_number(input: &str, at: &str, number: &str) -> Result<Wide, ParseError> {
+    number.parse::<Wide>().map_err(|_| {
+        let position = Position::within(input, at);
+        if number.bytes().all(|byte| byte.is_ascii_digit()) {
+            ParseError::Overflow { position }
+        } else {
+            ParseError::InvalidNumber {
+                position,
+                text: number.to_string(),
+            }
+        }
+    })
+}
 
-    let time = time.into_iter().fold(String::new(), |mut acc, t| {
-        acc.push_str(t);
-        acc
-    });
+/// Number of ways to beat the record of a race, `None` when the race is
+/// too long for [`Wide`]. Holding the button for `hold` goes
+/// `hold * (time - hold)`, which is furthest halfway through the race, so
+/// the holds that win are a range around the middle
+// `Wide` is only `Copy` without the `bigint` feature, so borrow operands
+#[allow(clippy::op_ref)]
+fn ways_to_win(time: &Wide, distance: &Wide) -> Option<Wide> {
+    let n = |n: u32| Wide::from(n);
+    let square = checked_mul(time, time)?;
+    // nothing below goes past the square of the time
+    let beats_record = |hold: &Wide| &(hold * (time - hold)) > distance;
 
-    let distance = distance.into_iter().fold(String::new(), |mut acc, d| {
-        acc.push_str(d);
-        acc
-    });
+    if !beats_record(&(time / n(2))) {
+        return Some(n(0));
+    }
 
-    let mut ways_to_win: u64 = 0;
-    println!("Time: {} Distance: {}", time, distance);
-    let time = time.parse::<u64>().unwrap();
-    let distance = distance.parse::<u64>().unwrap();
+    // the shortest hold that wins is within one of the lower root of
+    // hold * (time - hold) = distance
+    let discriminant = square - distance * n(4);
+    let mut first = (time - sqrt(&discriminant)) / n(2);
+    while first > n(0) && beats_record(&(&first - n(1))) {
+        first -= n(1);
+    }
+    while !beats_record(&first) {
+        first += n(1);
+    }
+
+    Some(time + n(1) - &first * n(2))
+}
+
+/// Race and how many ways there are to beat its record, as a step of an
+/// explanation
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Race {
+    #[cfg_attr(feature = "serde", serde(with = "common::wide_decimal"))]
+    pub time: Wide,
+    #[cfg_attr(feature = "serde", serde(with = "common::wide_decimal"))]
+    pub distance: Wide,
+    #[cfg_attr(feature = "serde", serde(with = "common::wide_decimal"))]
+    pub ways: Wide,
+}
+
+impl Race {
+    fn new(time: Wide, distance: Wide) -> Result<Self, ParseError> {
+        let ways = ways_to_win(&time, &distance).ok_or(ParseError::TooLarge)?;
+        Ok(Race {
+            time,
+            distance,
+            ways,
+        })
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
 
-    for speed in 1..time {
-        let time_moving = time - speed;
-        let dist = time_moving * speed;
-        if dist > distance {
-            ways_to_win += 1;
+    type Input = RaceSheet;
+
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        let (time, distance) = input.split_once('\n').ok_or_else(|| ParseError::Expected {
+            position: Position::end_of(input, input),
+            expected: "a distance line after the time line",
+        })?;
+        let (_, time) = time
+            .split_once("Time:")
+            .ok_or_else(|| ParseError::Expected {
+                position: Position::within(input, time),
+                expected: "'Time:'",
+            })?;
+        let time: Vec<&str> = time.split_ascii_whitespace().collect();
+        let (_, distance) =
+            distance
+                .split_once("Distance:")
+                .ok_or_else(|| ParseError::Expected {
+                    position: Position::within(input, distance),
+                    expected: "'Distance:'",
+                })?;
+        let distance: Vec<&str> = distance.split_ascii_whitespace().collect();
+
+        if time.is_empty() || time.len() != distance.len() {
+            return Err(ParseError::Expected {
+                position: Position::end_of(input, input),
+                expected: "a distance for every time",
+            });
         }
+
+        let races = time
+            .iter()
+            .zip(distance.iter())
+            .map(|(time, distance)| {
+                debug!("race of {time}ms with a record of {distance}mm");
+                Ok((
+                    parse_number(input, time, time)?,
+                    parse_number(input, distance, distance)?,
+                ))
+            })
+            .collect::<Result<Vec<(Wide, Wide)>, ParseError>>()?;
+
+        let long_time = time.iter().fold(String::new(), |mut acc, t| {
+            acc.push_str(t);
+            acc
+        });
+
+        let long_distance = distance.iter().fold(String::new(), |mut acc, d| {
+            acc.push_str(d);
+            acc
+        });
+
+        debug!("long race of {long_time}ms with a record of {long_distance}mm");
+        // every part is a valid number, so the whole can only be too long
+        let long_number = |at: &str, number: &str| {
+            number.parse::<Wide>().map_err(|_| ParseError::Overflow {
+                position: Position::within(input, at),
+            })
+        };
+        let long_race = (
+            long_number(time[0], &long_time)?,
+            long_number(distance[0], &long_distance)?,
+        );
+
+        Ok(RaceSheet { races, long_race })
+    }
+
+    fn part1(race_sheet: &Self::Input) -> Result<Answer, Self::Error> {
+        let ways_to_win = race_sheet
+            .races
+            .iter()
+            .map(|(time, distance)| ways_to_win(time, distance))
+            .collect::<Option<Vec<Wide>>>()
+            .ok_or(ParseError::TooLarge)?;
+
+        let product = checked_product(ways_to_win).map_err(|_| ParseError::TooLarge)?;
+        Ok(product.into())
     }
 
-    ways_to_win
+    fn part2(race_sheet: &Self::Input) -> Result<Option<Answer>, Self::Error> {
+        let (time, distance) = &race_sheet.long_race;
+        let ways_to_win = ways_to_win(time, distance).ok_or(ParseError::TooLarge)?;
+        Ok(Some(ways_to_win.into()))
+    }
+}
+
+pub fn try_part1(input: &str) -> Result<Answer, ParseError> {
+    Day6::part1(&Day6::parse(input)?)
+}
+
+pub fn try_part2(input: &str) -> Result<Answer, ParseError> {
+    let answer = Day6::part2(&Day6::parse(input)?)?;
+    Ok(answer.expect("part 2 is solved"))
+}
+
+pub fn part1(input: &str) -> Answer {
+    try_part1(input).unwrap()
+}
+
+pub fn part2(input: &str) -> Answer {
+    try_part2(input).unwrap()
+}
+
+/// Ways of solving each part, for timing against each other
+pub const VARIANTS: &[Variant<ParseError>] = &[
+    Variant::new("quadratic", 1, try_part1),
+    Variant::new("scan", 1, naive_part1).unless(naive::too_long_part1),
+    Variant::new("quadratic", 2, try_part2),
+    Variant::new("scan", 2, naive_part2).unless(naive::too_long_part2),
+];
+
+/// Part 1 along with the ways to win each race
+pub fn explain_part1(input: &str) -> Result<Explanation<Race>, ParseError> {
+    let steps = Day6::parse(input)?
+        .races
+        .into_iter()
+        .map(|(time, distance)| Race::new(time, distance))
+        .collect::<Result<Vec<Race>, ParseError>>()?;
+
+    let product =
+        checked_product(steps.iter().map(|step| &step.ways)).map_err(|_| ParseError::TooLarge)?;
+    Ok(Explanation::new(product, steps))
+}
+
+/// Part 2 along with the one long race
+pub fn explain_part2(input: &str) -> Result<Explanation<Race>, ParseError> {
+    let (time, distance) = Day6::parse(input)?.long_race;
+    let race = Race::new(time, distance)?;
+
+    let answer = Answer::from(&race.ways);
+    Ok(Explanation::new(answer, vec![race]))
+}
+
+/// Every number in the race sheet that isn't one, and any error in its
+/// layout, where parsing stops at the first
+pub fn lint(input: &str) -> Vec<ParseError> {
+    let mut errors: Vec<ParseError> = input
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .flat_map(|(_, numbers)| numbers.split_ascii_whitespace())
+        .filter_map(|number| parse_number(input, number, number).err())
+        .collect();
+
+    if let Err(err) = Day6::parse(input) {
+        if !errors.contains(&err) {
+            errors.push(err);
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = include_str!("../input.txt");
+    #[test]
+    fn model_test() {
+        let race_sheet = Day6::parse("Time:      7  15   30\nDistance:  9  40  200").unwrap();
+
+        assert_eq!(
+            race_sheet.races(),
+            [(7u32, 9u32), (15, 40), (30, 200)]
+                .map(|(time, distance)| (time.into(), distance.into()))
+        );
+        assert_eq!(
+            race_sheet.long_race(),
+            (&Wide::from(71530u32), &Wide::from(940200u32))
+        );
+    }
+
+    #[test]
+    fn explain_test() {
+        let input = include_str!("../examples/example.txt");
+        let explanation = explain_part1(input).unwrap();
+
+        assert_eq!(explanation.answer, Answer::from(288));
+        assert_eq!(
+            explanation.steps[0],
+            Race {
+                time: Wide::from(7u32),
+                distance: Wide::from(9u32),
+                ways: Wide::from(4u32),
+            }
+        );
+        assert_eq!(
+            explain_part2(input).unwrap().steps,
+            [Race {
+                time: Wide::from(71530u32),
+                distance: Wide::from(940200u32),
+                ways: Wide::from(71503u32),
+            }]
+        );
+    }
+
+    #[test]
+    fn long_race_test() {
+        // a long race past u64, whose square is past u128
+        let input = "Time:      4000000000  4000000000\nDistance:  1  2";
+
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(
+            try_part2(input).unwrap_err().to_string(),
+            "too large to solve without the `bigint` feature"
+        );
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(try_part2(input), Err(ParseError::TooLarge));
+        #[cfg(feature = "bigint")]
+        assert_eq!(try_part2(input).unwrap(), 40000000003999999999u128);
+        assert_eq!(try_part1(input).unwrap(), 3999999999u128 * 3999999999);
+    }
 
     #[test]
-    fn part1_test() {
-        let result = part1(INPUT);
-        assert_eq!(result, 288);
+    fn large_race_test() {
+        // each race past u32
+        let input = "Time:      5000000000\nDistance:  1";
+
+        assert_eq!(try_part1(input).unwrap(), 4999999999u64);
+        assert_eq!(try_part2(input).unwrap(), 4999999999u64);
+        assert_eq!(lint(input), []);
+
+        // a race past u128 on its own
+        let input = format!("Time:      {}\nDistance:  1", "9".repeat(40));
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(
+            try_part1(&input),
+            Err(ParseError::Overflow {
+                position: Position {
+                    line: 1,
+                    column: 12
+                }
+            })
+        );
+        #[cfg(feature = "bigint")]
+        assert!(try_part1(&input).is_ok());
     }
 
     #[test]
-    fn part2_test() {
-        let result = part2(INPUT);
-        assert_eq!(result, 71503);
+    fn lint_test() {
+        let input = "Time:      7  1S   30\nDistance:  9  4O";
+        let errors: Vec<String> = lint(input).iter().map(ParseError::to_string).collect();
+
+        assert_eq!(
+            errors,
+            [
+                "1:15: expected a number, found `1S`",
+                "2:15: expected a number, found `4O`",
+                "2:17: expected a distance for every time",
+            ]
+        );
+        assert_eq!(lint(include_str!("../examples/example.txt")), []);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let invalid_distance = "Time:      7  15   30\nDistance:  9  4O  200";
+        let missing_distance = "Time:      7  15   30\nDistance:  9  40";
+
+        assert_eq!(
+            try_part1(invalid_distance),
+            Err(ParseError::InvalidNumber {
+                position: Position {
+                    line: 2,
+                    column: 15
+                },
+                text: "4O".to_string(),
+            })
+        );
+        assert_eq!(
+            try_part2(missing_distance).unwrap_err().to_string(),
+            "2:17: expected a distance for every time"
+        );
     }
 }
@@ -0,0 +1,123 @@
+use common::{Answer, Rng, Solution, Wide};
+
+use crate::{Day6, ParseError};
+
+/// Race time or distance as a `u64`, which the naive solvers are only for
+fn small(n: &Wide) -> Result<u64, ParseError> {
+    n.to_string().parse().map_err(|_| ParseError::TooLarge)
+}
+
+/// Ways to beat the record, by trying every hold of the button. Fails
+/// when a hold would go further than a `u64` can hold
+fn naive_ways(time: &Wide, distance: &Wide) -> Result<u64, ParseError> {
+    let (time, distance) = (small(time)?, small(distance)?);
+    // holding for half the race goes furthest, so if that fits every
+    // other hold does
+    (time / 2)
+        .checked_mul(time - time / 2)
+        .ok_or(ParseError::TooLarge)?;
+
+    Ok((0..=time)
+        .filter(|hold| hold * (time - hold) > distance)
+        .count() as u64)
+}
+
+/// Longest race `aoc compare` tries every hold of
+const MAX_TIME: u64 = 100_000_000;
+
+/// Why trying every hold of a race `time` long would take too long
+fn too_long(time: &Wide) -> Option<String> {
+    (time > &Wide::from(MAX_TIME))
+        .then(|| format!("a {time} ms race is too long to try every hold"))
+}
+
+/// Why [`naive_part1`] would take too long on `input`
+pub(crate) fn too_long_part1(input: &str) -> Option<String> {
+    Day6::parse(input)
+        .ok()?
+        .races()
+        .iter()
+        .find_map(|(time, _)| too_long(time))
+}
+
+/// Why [`naive_part2`] would take too long on `input`
+pub(crate) fn too_long_part2(input: &str) -> Option<String> {
+    too_long(Day6::parse(input).ok()?.long_race().0)
+}
+
+/// Part 1 by trying every hold in every race, as a reference for
+/// [`crate::try_part1`]
+pub fn naive_part1(input: &str) -> Result<Answer, ParseError> {
+    let product =
+        Day6::parse(input)?
+            .races()
+            .iter()
+            .try_fold(1u128, |product, (time, distance)| {
+                product
+                    .checked_mul(naive_ways(time, distance)?.into())
+                    .ok_or(ParseError::TooLarge)
+            })?;
+
+    Ok(product.into())
+}
+
+/// Part 2 by trying every hold in the long race, as a reference for
+/// [`crate::try_part2`]. Only for short races
+pub fn naive_part2(input: &str) -> Result<Answer, ParseError> {
+    let race_sheet = Day6::parse(input)?;
+    let (time, distance) = race_sheet.long_race();
+
+    Ok(naive_ways(time, distance)?.into())
+}
+
+/// Race sheet small enough for the naive solvers, of one or two races up
+/// to 30 milliseconds long with records that may not be beatable
+pub fn generate_small(rng: &mut Rng) -> String {
+    let races: Vec<(u64, u64)> = (0..rng.range(1..3))
+        .map(|_| {
+            let time = rng.range(0..31);
+            (time, rng.range(0..time * time / 4 + 3))
+        })
+        .collect();
+
+    let (times, distances): (Vec<String>, Vec<String>) = races
+        .iter()
+        .map(|(time, distance)| (format!("{time:>4}"), format!("{distance:>4}")))
+        .unzip();
+
+    format!(
+        "Time:    {}\nDistance:{}\n",
+        times.join(" "),
+        distances.join(" ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_long_test() {
+        let example = include_str!("../examples/example.txt");
+        assert_eq!(too_long_part1(example), None);
+        assert_eq!(too_long_part2(example), None);
+
+        let input = "Time: 100000 001\nDistance: 1 0";
+        assert_eq!(too_long_part1(input), None);
+        assert_eq!(
+            too_long_part2(input),
+            Some("a 100000001 ms race is too long to try every hold".to_string())
+        );
+    }
+
+    #[test]
+    fn overflow_test() {
+        let overflow = Err(ParseError::TooLarge);
+        assert_eq!(naive_part1("Time: 10000000000\nDistance: 1"), overflow);
+        assert_eq!(naive_part2("Time: 100000 00000\nDistance: 1 0"), overflow);
+        assert_eq!(
+            naive_part1("Time: 7\nDistance: 18446744073709551616"),
+            overflow
+        );
+    }
+}
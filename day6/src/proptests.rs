@@ -0,0 +1,52 @@
+use proptest::prelude::*;
+
+use super::*;
+
+/// Ways to beat the record from the roots of `speed * (time - speed) = distance`
+fn ways_to_win(time: u64, distance: u64) -> u64 {
+    let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+        return 0;
+    };
+
+    // start just below the lower root and step up to the first winning speed
+    let mut speed = ((time - discriminant.isqrt()) / 2).saturating_sub(1);
+    while speed <= time / 2 && speed * (time - speed) <= distance {
+        speed += 1;
+    }
+
+    match speed <= time / 2 {
+        true => time - 2 * speed + 1,
+        false => 0,
+    }
+}
+
+proptest! {
+    #[test]
+    fn parse_never_panics(input in "\\PC*") {
+        let _ = Day6::parse(&input);
+    }
+
+    #[test]
+    fn part1_matches_quadratic_roots(races in prop::collection::vec((1..60u64, 0..1000u64), 1..4)) {
+        let times = races.iter().map(|(time, _)| time.to_string()).collect::<Vec<String>>();
+        let distances = races
+            .iter()
+            .map(|(_, distance)| distance.to_string())
+            .collect::<Vec<String>>();
+        let input = format!("Time: {}\nDistance: {}", times.join(" "), distances.join(" "));
+
+        let expected: u64 = races
+            .iter()
+            .map(|&(time, distance)| ways_to_win(time, distance))
+            .product();
+        prop_assert_eq!(part1(&input), expected);
+    }
+
+    #[test]
+    fn single_race_is_its_own_long_race(time in 1..5000u64, distance in 0..5_000_000u64) {
+        let input = format!("Time: {time}\nDistance: {distance}");
+
+        prop_assert_eq!(part1(&input), part2(&input));
+        prop_assert_eq!(part2(&input), ways_to_win(time, distance));
+    }
+}
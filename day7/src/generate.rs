@@ -0,0 +1,33 @@
+use std::fmt::Write;
+
+use common::Rng;
+
+use crate::{CARDS, HAND_SIZE};
+
+/// List of `size` hands with bids up to 1000
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let cards: Vec<char> = CARDS.chars().collect();
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let hand: String = (0..HAND_SIZE).map(|_| *rng.pick(&cards)).collect();
+        writeln!(input, "{hand} {}", rng.range(1..1001)).unwrap();
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let input = generate(&mut Rng::new(1), 100);
+
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        assert_eq!(input.lines().count(), 100);
+        assert!(crate::try_part1(&input).is_ok());
+        assert!(crate::try_part2(&input).is_ok());
+    }
+}
@@ -1,7 +1,123 @@
-use std::{cmp::Ordering, collections::HashMap, str::FromStr};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt::Display,
+    io::BufRead,
+    str::FromStr,
+};
+
+use common::{for_each_line, par, Answer, Explanation, Position, ReadError, Solution, Variant};
+use log::{debug, trace};
+
+mod generate;
+mod naive;
+#[cfg(test)]
+mod proptests;
+
+pub use generate::generate;
+pub use naive::{generate_small, naive_part1, naive_part2};
+
+const CARDS: &str = "23456789TJQKA";
+const HAND_SIZE: usize = 5;
+
+/// Error from reading the list of hands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Syntax that a hand line is missing
+    Expected {
+        position: Position,
+        expected: &'static str,
+    },
+    /// A card label that isn't one of A, K, Q, J, T or 9 to 2
+    InvalidCard { position: Position, card: char },
+    /// A hand without exactly five cards
+    HandSize { position: Position, found: usize },
+    /// A bid that isn't a number
+    InvalidNumber { position: Position, text: String },
+}
+
+impl ParseError {
+    pub fn position(&self) -> Position {
+        match self {
+            ParseError::Expected { position, .. }
+            | ParseError::InvalidCard { position, .. }
+            | ParseError::HandSize { position, .. }
+            | ParseError::InvalidNumber { position, .. } => *position,
+        }
+    }
+
+    fn on_line(mut self, lines: usize) -> Self {
+        match &mut self {
+            ParseError::Expected { position, .. }
+            | ParseError::InvalidCard { position, .. }
+            | ParseError::HandSize { position, .. }
+            | ParseError::InvalidNumber { position, .. } => *position = position.on_line(lines),
+        }
+        self
+    }
+}
 
-#[derive(PartialEq, PartialOrd, Debug, Eq, Ord)]
-enum HandType {
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Expected { position, expected } => {
+                write!(f, "{position}: expected {expected}")
+            }
+            ParseError::InvalidCard { position, card } => {
+                write!(
+                    f,
+                    "{position}: expected a card from {CARDS}, found `{card}`"
+                )
+            }
+            ParseError::HandSize { position, found } => {
+                write!(f, "{position}: expected {HAND_SIZE} cards, found {found}")
+            }
+            ParseError::InvalidNumber { position, text } => {
+                write!(f, "{position}: expected a number, found `{text}`")
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Split a hand line into its validated cards and bid
+fn parse_hand_and_bid(s: &str) -> Result<(Vec<char>, u32), ParseError> {
+    let (hand, bid) = s.split_once(' ').ok_or_else(|| ParseError::Expected {
+        position: Position::end_of(s, s),
+        expected: "a bid after the hand",
+    })?;
+
+    for (idx, card) in hand.char_indices() {
+        if !CARDS.contains(card) {
+            return Err(ParseError::InvalidCard {
+                position: Position::within(s, &hand[idx..]),
+                card,
+            });
+        }
+    }
+
+    let hand: Vec<char> = hand.chars().collect();
+    if hand.len() != HAND_SIZE {
+        return Err(ParseError::HandSize {
+            position: Position::within(s, s),
+            found: hand.len(),
+        });
+    }
+
+    let bid = bid.parse::<u32>().map_err(|_| ParseError::InvalidNumber {
+        position: Position::within(s, bid),
+        text: bid.to_string(),
+    })?;
+
+    Ok((hand, bid))
+}
+
+/// Kind of a hand, weakest first
+#[derive(PartialEq, PartialOrd, Debug, Clone, Copy, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HandType {
     HighCard,
     Pair,
     TwoPair,
@@ -11,21 +127,108 @@ enum HandType {
     FiveKind,
 }
 
-#[derive(Debug)]
-struct CamelCard {
+/// A hand and its bid, typed with the standard rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "HandData", into = "HandData")
+)]
+pub struct CamelCard {
     hand: Vec<char>,
     bid: u32,
     hand_type: HandType,
 }
 
-#[derive(Debug)]
-struct CamelCardJoker {
+/// A hand and its bid, typed with jacks as jokers
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "HandData", into = "HandData")
+)]
+pub struct CamelCardJoker {
     hand: Vec<char>,
     bid: u32,
     hand_type: HandType,
 }
 
+/// Check that a hand has [`HAND_SIZE`] cards from [`CARDS`]
+fn check_hand(hand: &[char]) -> Result<(), String> {
+    if let Some(card) = hand.iter().find(|card| !CARDS.contains(**card)) {
+        return Err(format!("expected a card from {CARDS}, found `{card}`"));
+    }
+    if hand.len() != HAND_SIZE {
+        return Err(format!("expected {HAND_SIZE} cards, found {}", hand.len()));
+    }
+    Ok(())
+}
+
+/// Type of a hand from how many of each card it has
+fn hand_type(hand_cnt_map: &HashMap<char, u8>) -> HandType {
+    match hand_cnt_map.values().len() {
+        1 => HandType::FiveKind,
+        2 => {
+            if hand_cnt_map.values().any(|&cnt| cnt == 4) {
+                HandType::FourKind
+            } else {
+                HandType::FullHouse
+            }
+        }
+        3 => {
+            if hand_cnt_map.values().any(|&cnt| cnt == 3) {
+                HandType::ThreeKind
+            } else {
+                HandType::TwoPair
+            }
+        }
+        4 => HandType::Pair,
+        5 => HandType::HighCard,
+        _ => panic!("Can't determine hand type"),
+    }
+}
+
 impl CamelCard {
+    /// # Panics
+    ///
+    /// If a card isn't one of `23456789TJQKA`
+    pub fn new(hand: [char; HAND_SIZE], bid: u32) -> Self {
+        check_hand(&hand).unwrap_or_else(|err| panic!("{err}"));
+        CamelCard::with_hand(hand.to_vec(), bid)
+    }
+
+    fn with_hand(hand: Vec<char>, bid: u32) -> Self {
+        let mut hand_cnt_map: HashMap<char, u8> = HashMap::new();
+
+        for c in &hand {
+            *hand_cnt_map.entry(*c).or_default() += 1;
+        }
+
+        CamelCard {
+            hand_type: hand_type(&hand_cnt_map),
+            hand,
+            bid,
+        }
+    }
+
+    pub fn hand(&self) -> &[char] {
+        &self.hand
+    }
+
+    pub fn bid(&self) -> u32 {
+        self.bid
+    }
+
+    pub fn hand_type(&self) -> HandType {
+        self.hand_type
+    }
+
+    /// Rank of the hand as a single number, where stronger hands are
+    /// greater and equal hands are equal
+    pub fn strength(&self) -> u32 {
+        strength(&self.hand_type, &self.hand, CamelCard::get_card_rank)
+    }
+
     fn get_card_rank(card: char) -> u32 {
         match card {
             'A' => 13,
@@ -47,6 +250,36 @@ impl CamelCard {
 }
 
 impl CamelCardJoker {
+    /// # Panics
+    ///
+    /// If a card isn't one of `23456789TJQKA`
+    pub fn new(hand: [char; HAND_SIZE], bid: u32) -> Self {
+        check_hand(&hand).unwrap_or_else(|err| panic!("{err}"));
+        CamelCardJoker::with_hand(hand.to_vec(), bid)
+    }
+
+    pub fn hand(&self) -> &[char] {
+        &self.hand
+    }
+
+    pub fn bid(&self) -> u32 {
+        self.bid
+    }
+
+    pub fn hand_type(&self) -> HandType {
+        self.hand_type
+    }
+
+    /// Rank of the hand as a single number, where stronger hands are
+    /// greater and equal hands are equal
+    pub fn strength(&self) -> u32 {
+        strength(
+            &self.hand_type,
+            &self.hand,
+            CamelCardJoker::get_card_rank_with_joker,
+        )
+    }
+
     fn get_card_rank_with_joker(card: char) -> u32 {
         match card {
             'A' => 13,
@@ -68,67 +301,46 @@ impl CamelCardJoker {
     }
 }
 
-impl FromStr for CamelCard {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (hand, bid) = s.split_once(' ').unwrap();
-
-        let hand: Vec<char> = hand.chars().collect();
-        let mut hand_cnt_map: HashMap<char, u8> = HashMap::new();
+/// Canonical text of a hand, its cards then its bid
+impl Display for CamelCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_hand(f, &self.hand, self.bid)
+    }
+}
 
-        for c in &hand {
-            match hand_cnt_map.get(c) {
-                Some(cnt) => hand_cnt_map.insert(*c, *cnt + 1),
-                None => hand_cnt_map.insert(*c, 1),
-            };
-        }
+impl Display for CamelCardJoker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_hand(f, &self.hand, self.bid)
+    }
+}
 
-        let hand_type = match hand_cnt_map.values().len() {
-            1 => HandType::FiveKind,
-            2 => {
-                let mut hand_type = HandType::FullHouse;
-                for (_, cnt) in hand_cnt_map.into_iter() {
-                    // if card is joker then add to the highest count.
+fn write_hand(f: &mut std::fmt::Formatter<'_>, hand: &[char], bid: u32) -> std::fmt::Result {
+    for card in hand {
+        write!(f, "{card}")?;
+    }
+    write!(f, " {bid}")
+}
 
-                    if cnt == 1 || cnt == 4 {
-                        hand_type = HandType::FourKind;
-                    }
-                    break;
-                }
-                hand_type
-            }
-            3 => {
-                let mut hand_type = HandType::TwoPair;
-                for (card, cnt) in hand_cnt_map.into_iter() {
-                    // if card is joker then add to the highest count.
-                    if cnt == 3 {
-                        hand_type = HandType::ThreeKind;
-                        break;
-                    }
-                }
-                hand_type
-            }
-            4 => HandType::Pair,
-            5 => HandType::HighCard,
-            _ => panic!("Can't determine hand type"),
-        };
+impl FromStr for CamelCard {
+    type Err = ParseError;
 
-        Ok(CamelCard {
-            hand,
-            bid: bid.parse::<u32>().unwrap(),
-            hand_type,
-        })
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hand, bid) = parse_hand_and_bid(s)?;
+        Ok(CamelCard::with_hand(hand, bid))
     }
 }
 
 impl FromStr for CamelCardJoker {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (hand, bid) = s.split_once(' ').unwrap();
+        let (hand, bid) = parse_hand_and_bid(s)?;
+        Ok(CamelCardJoker::with_hand(hand, bid))
+    }
+}
 
-        let hand: Vec<char> = hand.chars().collect();
+impl CamelCardJoker {
+    fn with_hand(hand: Vec<char>, bid: u32) -> Self {
         let mut hand_cnt_map: HashMap<char, u8> = HashMap::with_capacity(5);
         let mut joker_cnt: u8 = 0;
         let mut max_key_value = (' ', 0);
@@ -137,7 +349,7 @@ impl FromStr for CamelCardJoker {
             match hand_cnt_map.get(c) {
                 Some(cnt) => {
                     if *c == 'J' {
-                        joker_cnt = joker_cnt + 1;
+                        joker_cnt += 1;
                     } else {
                         let new_cnt = *cnt + 1;
                         hand_cnt_map.insert(*c, new_cnt);
@@ -156,7 +368,7 @@ impl FromStr for CamelCardJoker {
                 }
                 None => {
                     if *c == 'J' {
-                        joker_cnt = joker_cnt + 1;
+                        joker_cnt += 1;
                     } else {
                         hand_cnt_map.insert(*c, 1);
                         let curr_max_card =
@@ -174,126 +386,408 @@ impl FromStr for CamelCardJoker {
 
         if max_key_value.0 != ' ' {
             hand_cnt_map.insert(max_key_value.0, max_key_value.1 + joker_cnt);
-        } else if hand_cnt_map.len() == 0 {
+        } else if hand_cnt_map.is_empty() {
             // all jokers
             hand_cnt_map.insert('A', 5);
         }
 
-        let hand_type = match hand_cnt_map.values().len() {
-            1 => HandType::FiveKind,
-            2 => {
-                let mut hand_type = HandType::FullHouse;
-                for (_, cnt) in hand_cnt_map.into_iter() {
-                    if cnt == 1 || cnt == 4 {
-                        hand_type = HandType::FourKind;
-                    }
-                    break;
-                }
-                hand_type
-            }
-            3 => {
-                let mut hand_type = HandType::TwoPair;
-                for (_, cnt) in hand_cnt_map.into_iter() {
-                    if cnt == 3 {
-                        hand_type = HandType::ThreeKind;
-                        break;
-                    }
-                }
-                hand_type
-            }
-            4 => HandType::Pair,
-            5 => HandType::HighCard,
-            _ => panic!("Can't determine hand type"),
-        };
+        CamelCardJoker {
+            hand_type: hand_type(&hand_cnt_map),
+            hand,
+            bid,
+        }
+    }
+}
+
+/// Order of a hand as a single number: its type, then each card's rank
+/// from first to last. Ranks are at most 13, so each card takes one base
+/// 14 digit
+fn strength(hand_type: &HandType, hand: &[char], rank: fn(char) -> u32) -> u32 {
+    hand.iter().fold(
+        match hand_type {
+            HandType::HighCard => 0,
+            HandType::Pair => 1,
+            HandType::TwoPair => 2,
+            HandType::ThreeKind => 3,
+            HandType::FullHouse => 4,
+            HandType::FourKind => 5,
+            HandType::FiveKind => 6,
+        },
+        |strength, card| strength * 14 + rank(*card),
+    )
+}
+
+/// Bids seen so far for one distinct hand
+#[derive(Default)]
+struct Tally {
+    count: u64,
+    bids: u64,
+    /// Each bid times the number of identical hands before it, which
+    /// rank below it like they would in a stable sort
+    tie_bids: u128,
+}
+
+/// Winnings of hands streamed in one at a time. Only one tally is kept
+/// per distinct hand, so memory is bounded by the 13^5 possible hands
+/// rather than by the length of the input
+#[derive(Default)]
+struct Standings {
+    tallies: BTreeMap<u32, Tally>,
+}
+
+impl Standings {
+    fn add(&mut self, strength: u32, bid: u32) {
+        let tally = self.tallies.entry(strength).or_default();
+        tally.tie_bids += u128::from(bid) * u128::from(tally.count);
+        tally.bids += u64::from(bid);
+        tally.count += 1;
+    }
+
+    fn winnings(&self) -> u128 {
+        let mut ranked: u64 = 0;
+        let mut total: u128 = 0;
+
+        for tally in self.tallies.values() {
+            total += u128::from(tally.bids) * u128::from(ranked + 1) + tally.tie_bids;
+            ranked += tally.count;
+        }
+
+        total
+    }
+}
+
+/// Every hand in the puzzle input, read both with the standard rules
+/// and with jacks as jokers. With the `serde` feature it's stored as the
+/// list of hands
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Vec<CamelCard>", into = "Vec<CamelCard>")
+)]
+pub struct CamelCards {
+    hands: Vec<CamelCard>,
+    joker_hands: Vec<CamelCardJoker>,
+}
+
+impl CamelCards {
+    pub fn new(hands: Vec<CamelCard>) -> Self {
+        let joker_hands = hands
+            .iter()
+            .map(|card| CamelCardJoker::with_hand(card.hand.clone(), card.bid))
+            .collect();
+
+        CamelCards { hands, joker_hands }
+    }
+
+    /// Hands with the standard rules, in input order
+    pub fn hands(&self) -> &[CamelCard] {
+        &self.hands
+    }
+
+    /// Hands with jacks as jokers, in input order
+    pub fn joker_hands(&self) -> &[CamelCardJoker] {
+        &self.joker_hands
+    }
+}
+
+/// Canonical text of the list of hands, one per line in input order
+impl Display for CamelCards {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for hand in &self.hands {
+            writeln!(f, "{hand}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Vec<CamelCard>> for CamelCards {
+    fn from(hands: Vec<CamelCard>) -> Self {
+        CamelCards::new(hands)
+    }
+}
+
+impl From<CamelCards> for Vec<CamelCard> {
+    fn from(camel_cards: CamelCards) -> Self {
+        camel_cards.hands
+    }
+}
+
+/// A hand as it's serialized, without its type as that's worked out
+/// from the cards
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HandData {
+    hand: String,
+    bid: u32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<HandData> for CamelCard {
+    type Error = String;
+
+    fn try_from(data: HandData) -> Result<Self, Self::Error> {
+        let hand: Vec<char> = data.hand.chars().collect();
+        check_hand(&hand)?;
+        Ok(CamelCard::with_hand(hand, data.bid))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<CamelCard> for HandData {
+    fn from(card: CamelCard) -> Self {
+        HandData {
+            hand: card.hand.iter().collect(),
+            bid: card.bid,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<HandData> for CamelCardJoker {
+    type Error = String;
+
+    fn try_from(data: HandData) -> Result<Self, Self::Error> {
+        let hand: Vec<char> = data.hand.chars().collect();
+        check_hand(&hand)?;
+        Ok(CamelCardJoker::with_hand(hand, data.bid))
+    }
+}
 
-        Ok(CamelCardJoker {
+#[cfg(feature = "serde")]
+impl From<CamelCardJoker> for HandData {
+    fn from(card: CamelCardJoker) -> Self {
+        HandData {
+            hand: card.hand.iter().collect(),
+            bid: card.bid,
+        }
+    }
+}
+
+/// Where a hand ended up in the ranking and what it won, as a step of an
+/// explanation
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ranking {
+    pub rank: u64,
+    pub hand: String,
+    pub hand_type: HandType,
+    pub bid: u32,
+    pub winnings: u128,
+}
+
+/// Rank hands given as their text, type, bid and strength, weakest first
+/// with equal hands kept in input order
+fn explain_ranking(mut hands: Vec<(String, HandType, u32, u32)>) -> Explanation<Ranking> {
+    hands.sort_by_key(|&(_, _, _, strength)| strength);
+
+    let steps: Vec<Ranking> = (1..)
+        .zip(hands)
+        .map(|(rank, (hand, hand_type, bid, _))| Ranking {
+            rank,
             hand,
-            bid: bid.parse::<u32>().unwrap(),
             hand_type,
+            bid,
+            winnings: u128::from(rank) * u128::from(bid),
         })
-    }
+        .collect();
+
+    let total: u128 = steps.iter().map(|step| step.winnings).sum();
+    Explanation::new(total, steps)
 }
 
-pub fn part1(input: &str) -> u32 {
-    let mut camel_cards: Vec<CamelCard> = input
-        .lines()
-        .map(|line| line.parse::<CamelCard>().unwrap())
-        .collect();
+pub struct Day7;
 
-    camel_cards.sort_by(|c1, c2| match c1.hand_type.cmp(&c2.hand_type) {
-        Ordering::Greater => Ordering::Greater,
-        Ordering::Less => Ordering::Less,
-        Ordering::Equal => {
-            let card_zip = c1.hand.iter().zip(c2.hand.iter());
+impl Solution for Day7 {
+    const DAY: u8 = 7;
 
-            for (card1, card2) in card_zip {
-                let card1_val = CamelCard::get_card_rank(*card1);
-                let card2_val = CamelCard::get_card_rank(*card2);
+    type Input = CamelCards;
 
-                if card1_val > card2_val {
-                    return Ordering::Greater;
-                }
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        let lines: Vec<&str> = input.lines().collect();
+
+        Ok(CamelCards {
+            hands: par::try_map(&lines, |idx, line| {
+                line.parse::<CamelCard>().map_err(|err| err.on_line(idx))
+            })?,
+            joker_hands: par::try_map(&lines, |idx, line| {
+                line.parse::<CamelCardJoker>()
+                    .map_err(|err| err.on_line(idx))
+            })?,
+        })
+    }
+
+    fn part1(camel_cards: &Self::Input) -> Result<Answer, Self::Error> {
+        let mut camel_cards: Vec<&CamelCard> = camel_cards.hands.iter().collect();
+
+        par::sort_by(&mut camel_cards, |c1, c2| {
+            match c1.hand_type.cmp(&c2.hand_type) {
+                Ordering::Greater => Ordering::Greater,
+                Ordering::Less => Ordering::Less,
+                Ordering::Equal => {
+                    let card_zip = c1.hand.iter().zip(c2.hand.iter());
 
-                if card1_val < card2_val {
-                    return Ordering::Less;
+                    for (card1, card2) in card_zip {
+                        let card1_val = CamelCard::get_card_rank(*card1);
+                        let card2_val = CamelCard::get_card_rank(*card2);
+
+                        if card1_val > card2_val {
+                            return Ordering::Greater;
+                        }
+
+                        if card1_val < card2_val {
+                            return Ordering::Less;
+                        }
+                    }
+
+                    Ordering::Equal
                 }
             }
+        });
 
-            Ordering::Equal
+        let mut total: u128 = 0;
+
+        for (multiplier, camel_card) in (1..).zip(camel_cards) {
+            total += u128::from(camel_card.bid) * multiplier;
         }
-    });
 
-    let mut total = 0;
-    let mut multiplier = 1;
+        Ok(total.into())
+    }
+
+    fn part2(camel_cards: &Self::Input) -> Result<Option<Answer>, Self::Error> {
+        let mut camel_cards: Vec<&CamelCardJoker> = camel_cards.joker_hands.iter().collect();
+
+        par::sort_by(&mut camel_cards, |c1, c2| {
+            match c1.hand_type.cmp(&c2.hand_type) {
+                Ordering::Greater => Ordering::Greater,
+                Ordering::Less => Ordering::Less,
+                Ordering::Equal => {
+                    let card_zip = c1.hand.iter().zip(c2.hand.iter());
+
+                    for (card1, card2) in card_zip {
+                        let card1_val = CamelCardJoker::get_card_rank_with_joker(*card1);
+                        let card2_val = CamelCardJoker::get_card_rank_with_joker(*card2);
 
-    for camel_card in &camel_cards {
-        total = total + (camel_card.bid * multiplier);
-        multiplier = multiplier + 1;
+                        if card1_val > card2_val {
+                            return Ordering::Greater;
+                        }
+
+                        if card1_val < card2_val {
+                            return Ordering::Less;
+                        }
+                    }
+
+                    Ordering::Equal
+                }
+            }
+        });
+        debug!("ranking {} hands with jokers", camel_cards.len());
+
+        let mut total: u128 = 0;
+
+        for (multiplier, camel_card) in (1..).zip(camel_cards) {
+            trace!("rank {multiplier}: {camel_card:?}");
+            total += u128::from(camel_card.bid) * multiplier;
+        }
+
+        Ok(Some(total.into()))
     }
+}
+
+pub fn try_part1(input: &str) -> Result<Answer, ParseError> {
+    Day7::part1(&Day7::parse(input)?)
+}
+
+pub fn try_part2(input: &str) -> Result<Answer, ParseError> {
+    let answer = Day7::part2(&Day7::parse(input)?)?;
+    Ok(answer.expect("part 2 is solved"))
+}
 
-    total
+pub fn part1(input: &str) -> Answer {
+    try_part1(input).unwrap()
 }
 
-pub fn part2(input: &str) -> u32 {
-    let mut camel_cards: Vec<CamelCardJoker> = input
+pub fn part2(input: &str) -> Answer {
+    try_part2(input).unwrap()
+}
+
+/// Ways of solving each part, for timing against each other
+pub const VARIANTS: &[Variant<ParseError>] = &[
+    Variant::new("joker-counts", 2, try_part2),
+    Variant::new("substitution", 2, naive_part2),
+];
+
+/// Every hand line that doesn't parse, where parsing stops at the first
+pub fn lint(input: &str) -> Vec<ParseError> {
+    input
         .lines()
-        .map(|line| line.parse::<CamelCardJoker>().unwrap())
+        .enumerate()
+        .filter_map(|(idx, line)| line.parse::<CamelCard>().err().map(|err| err.on_line(idx)))
+        .collect()
+}
+
+/// Part 1 along with every hand in the order they rank
+pub fn explain_part1(input: &str) -> Result<Explanation<Ranking>, ParseError> {
+    let hands = Day7::parse(input)?
+        .hands
+        .iter()
+        .map(|card| {
+            let hand = card.hand.iter().collect();
+            (hand, card.hand_type, card.bid, card.strength())
+        })
         .collect();
 
-    camel_cards.sort_by(|c1, c2| match c1.hand_type.cmp(&c2.hand_type) {
-        Ordering::Greater => Ordering::Greater,
-        Ordering::Less => Ordering::Less,
-        Ordering::Equal => {
-            let card_zip = c1.hand.iter().zip(c2.hand.iter());
+    Ok(explain_ranking(hands))
+}
 
-            for (card1, card2) in card_zip {
-                let card1_val = CamelCardJoker::get_card_rank_with_joker(*card1);
-                let card2_val = CamelCardJoker::get_card_rank_with_joker(*card2);
+/// Part 2 along with every hand in the order they rank with jokers
+pub fn explain_part2(input: &str) -> Result<Explanation<Ranking>, ParseError> {
+    let hands = Day7::parse(input)?
+        .joker_hands
+        .iter()
+        .map(|card| {
+            let hand = card.hand.iter().collect();
+            (hand, card.hand_type, card.bid, card.strength())
+        })
+        .collect();
 
-                if card1_val > card2_val {
-                    return Ordering::Greater;
-                }
+    Ok(explain_ranking(hands))
+}
 
-                if card1_val < card2_val {
-                    return Ordering::Less;
-                }
-            }
+/// Part 1 over a list of hands streamed line by line, for inputs too
+/// large to read into memory
+pub fn part1_reader(reader: impl BufRead) -> Result<Answer, ReadError<ParseError>> {
+    let mut standings = Standings::default();
 
-            Ordering::Equal
-        }
-    });
-    println!("{}", camel_cards.len());
+    for_each_line(reader, |idx, line| {
+        let card = line.parse::<CamelCard>().map_err(|err| err.on_line(idx))?;
+        standings.add(card.strength(), card.bid);
+        Ok(())
+    })?;
 
-    let mut total = 0;
-    let mut multiplier = 1;
+    Ok(standings.winnings().into())
+}
 
-    for camel_card in &camel_cards {
-        println!("{:?}", camel_card);
-        total = total + (camel_card.bid * multiplier);
-        multiplier = multiplier + 1;
-    }
+/// Part 2 over a list of hands streamed line by line
+pub fn part2_reader(reader: impl BufRead) -> Result<Answer, ReadError<ParseError>> {
+    let mut standings = Standings::default();
+
+    for_each_line(reader, |idx, line| {
+        let card = line
+            .parse::<CamelCardJoker>()
+            .map_err(|err| err.on_line(idx))?;
+        standings.add(card.strength(), card.bid);
+        Ok(())
+    })?;
+
+    Ok(standings.winnings().into())
+}
 
-    total
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
 }
 
 #[cfg(test)]
@@ -301,7 +795,16 @@ mod tests {
     use super::HandType::*;
     use super::*;
 
-    const INPUT: &'static str = include_str!("../input.txt");
+    #[test]
+    fn display_test() {
+        let input = format!("{}\n", include_str!("../examples/example.txt"));
+
+        assert_eq!(Day7::parse(&input).unwrap().to_string(), input);
+        assert_eq!(
+            CamelCardJoker::new(['T', '5', '5', 'J', '5'], 684).to_string(),
+            "T55J5 684"
+        );
+    }
 
     #[test]
     fn hand_type_test() {
@@ -350,12 +853,6 @@ mod tests {
         assert_eq!(four_sixes_with_jack.hand_type, FourKind);
     }
 
-    #[test]
-    fn part1_test() {
-        let result = part1(INPUT);
-        assert_eq!(result, 6440);
-    }
-
     #[test]
     fn part2_mini_test() {
         let input = "AAAJJ 1\nAJAAA 2";
@@ -364,8 +861,125 @@ mod tests {
     }
 
     #[test]
-    fn part2_test() {
-        let result = part2(INPUT);
-        assert_eq!(result, 5905);
+    fn large_bid_test() {
+        // winnings past u32
+        let input = "32T3K 4000000000\nT55J5 4000000000";
+        assert_eq!(part1(input), 12_000_000_000u64);
+        assert_eq!(part2(input), 12_000_000_000u64);
+        assert_eq!(
+            explain_part1(input).unwrap().steps[1].winnings,
+            8_000_000_000
+        );
+    }
+
+    #[test]
+    fn lint_test() {
+        let errors: Vec<String> = lint("32T3K 765\nT55X5 684\n32T3 765\nKK677 bid")
+            .iter()
+            .map(ParseError::to_string)
+            .collect();
+
+        assert_eq!(
+            errors,
+            [
+                "2:4: expected a card from 23456789TJQKA, found `X`",
+                "3:1: expected 5 cards, found 4",
+                "4:7: expected a number, found `bid`",
+            ]
+        );
+        assert_eq!(lint(include_str!("../examples/example.txt")), []);
+    }
+
+    #[test]
+    fn explain_test() {
+        let input = include_str!("../examples/example.txt");
+        let explanation = explain_part1(input).unwrap();
+        let ranking: Vec<(&str, HandType)> = explanation
+            .steps
+            .iter()
+            .map(|step| (step.hand.as_str(), step.hand_type))
+            .collect();
+
+        assert_eq!(explanation.answer, Answer::from(6440));
+        assert_eq!(
+            ranking,
+            [
+                ("32T3K", Pair),
+                ("KTJJT", TwoPair),
+                ("KK677", TwoPair),
+                ("T55J5", ThreeKind),
+                ("QQQJA", ThreeKind),
+            ]
+        );
+        assert_eq!(
+            explain_part2(input).unwrap().steps[4],
+            Ranking {
+                rank: 5,
+                hand: "KTJJT".to_string(),
+                hand_type: FourKind,
+                bid: 220,
+                winnings: 1100,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let invalid_card = "32T3K 765\nT55X5 684";
+        let short_hand = "32T3 765";
+
+        assert_eq!(
+            try_part1(invalid_card),
+            Err(ParseError::InvalidCard {
+                position: Position { line: 2, column: 4 },
+                card: 'X',
+            })
+        );
+        assert_eq!(
+            try_part2(short_hand).unwrap_err().to_string(),
+            "1:1: expected 5 cards, found 4"
+        );
+        assert_eq!(
+            "32T3K bid".parse::<CamelCard>().unwrap_err(),
+            ParseError::InvalidNumber {
+                position: Position { line: 1, column: 7 },
+                text: "bid".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn model_test() {
+        let card = CamelCard::new(['K', 'T', 'J', 'J', 'T'], 220);
+        let joker = CamelCardJoker::new(['K', 'T', 'J', 'J', 'T'], 220);
+
+        assert_eq!(card, "KTJJT 220".parse::<CamelCard>().unwrap());
+        assert_eq!(card.hand_type(), HandType::TwoPair);
+        assert_eq!(joker.hand_type(), HandType::FourKind);
+        assert!(CamelCard::new(['K', 'K', '6', '7', '7'], 28).strength() > card.strength());
+
+        let camel_cards = CamelCards::new(vec![card.clone()]);
+        assert_eq!(camel_cards.hands(), [card]);
+        assert_eq!(camel_cards.joker_hands(), [joker]);
+    }
+
+    #[test]
+    #[should_panic(expected = "found `X`")]
+    fn invalid_card_test() {
+        CamelCard::new(['X', 'T', 'J', 'J', 'T'], 220);
+    }
+
+    #[test]
+    fn reader_test() {
+        let input = include_str!("../examples/example.txt");
+
+        assert_eq!(part1_reader(input.as_bytes()).unwrap(), part1(input));
+        assert_eq!(part2_reader(input.as_bytes()).unwrap(), part2(input));
+        assert_eq!(
+            part1_reader("32T3K 765\n32T3 1".as_bytes())
+                .unwrap_err()
+                .to_string(),
+            "2:1: expected 5 cards, found 4"
+        );
     }
 }
@@ -0,0 +1,109 @@
+use std::fmt::Write;
+
+use common::{Answer, Rng, Solution};
+
+use crate::{Day7, HandType, ParseError, CARDS};
+
+/// Cards in order of strength with jacks as jokers
+const JOKER_CARDS: &str = "J23456789TQKA";
+
+/// Type of a hand from how many of each card it has, most first
+fn naive_type(hand: &[char]) -> HandType {
+    let mut counts: Vec<usize> = CARDS
+        .chars()
+        .map(|card| hand.iter().filter(|&&other| other == card).count())
+        .filter(|&count| count > 0)
+        .collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+
+    match counts[..] {
+        [5] => HandType::FiveKind,
+        [4, 1] => HandType::FourKind,
+        [3, 2] => HandType::FullHouse,
+        [3, 1, 1] => HandType::ThreeKind,
+        [2, 2, 1] => HandType::TwoPair,
+        [2, 1, 1, 1] => HandType::Pair,
+        _ => HandType::HighCard,
+    }
+}
+
+/// Best type of a hand with its jokers tried as every combination of
+/// the other cards from `first` on in [`CARDS`]. Jokers are filled in
+/// with cards that never go back in [`CARDS`], as which joker gets which
+/// card doesn't matter
+fn naive_joker_type(hand: &[char], first: usize) -> HandType {
+    let Some(joker) = hand.iter().position(|&card| card == 'J') else {
+        return naive_type(hand);
+    };
+
+    CARDS
+        .char_indices()
+        .skip(first)
+        .filter(|&(_, card)| card != 'J')
+        .map(|(idx, card)| {
+            let mut hand = hand.to_vec();
+            hand[joker] = card;
+            naive_joker_type(&hand, idx)
+        })
+        .max()
+        // the ace is always left to try
+        .unwrap()
+}
+
+/// Total winnings of hands ranked by their type and then their cards in
+/// the order of `cards`, equal hands keeping their order in the input
+fn naive_winnings(
+    hands: impl Iterator<Item = (Vec<char>, u32)>,
+    hand_type: impl Fn(&[char]) -> HandType,
+    cards: &str,
+) -> Answer {
+    let mut ranked: Vec<(HandType, Vec<usize>, u32)> = hands
+        .map(|(hand, bid)| {
+            let strengths = hand.iter().map(|&card| cards.find(card).unwrap());
+            (hand_type(&hand), strengths.collect(), bid)
+        })
+        .collect();
+    ranked.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+    let total: u128 = (1..)
+        .zip(&ranked)
+        .map(|(rank, (_, _, bid))| rank * u128::from(*bid))
+        .sum();
+    total.into()
+}
+
+/// Part 1 by ranking the hands with a plain sort, as a reference for
+/// [`crate::try_part1`]
+pub fn naive_part1(input: &str) -> Result<Answer, ParseError> {
+    let hands = Day7::parse(input)?.hands;
+    let hands = hands.iter().map(|hand| (hand.hand().to_vec(), hand.bid()));
+
+    Ok(naive_winnings(hands, naive_type, CARDS))
+}
+
+/// Part 2 by trying every card for every joker, as a reference for
+/// [`crate::try_part2`]
+pub fn naive_part2(input: &str) -> Result<Answer, ParseError> {
+    let hands = Day7::parse(input)?.hands;
+    let hands = hands.iter().map(|hand| (hand.hand().to_vec(), hand.bid()));
+
+    Ok(naive_winnings(
+        hands,
+        |hand| naive_joker_type(hand, 0),
+        JOKER_CARDS,
+    ))
+}
+
+/// Hands small enough for the naive solvers, drawn from a few cards
+/// including the jack so there are plenty of jokers and equal hands
+pub fn generate_small(rng: &mut Rng) -> String {
+    let cards = ['2', '7', 'J', 'Q', 'A'];
+    let mut input = String::new();
+
+    for _ in 0..rng.range(1..9) {
+        let hand: String = (0..5).map(|_| *rng.pick(&cards)).collect();
+        writeln!(input, "{hand} {}", rng.range(1..50)).unwrap();
+    }
+
+    input
+}
@@ -0,0 +1,70 @@
+use proptest::prelude::*;
+
+use super::*;
+
+fn hand() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop::sample::select(CARDS.chars().collect::<Vec<char>>()),
+        HAND_SIZE,
+    )
+    .prop_map(|cards| cards.into_iter().collect())
+}
+
+proptest! {
+    #[test]
+    fn parse_never_panics(input in "\\PC*") {
+        let _ = Day7::parse(&input);
+    }
+
+    #[test]
+    fn joker_never_lowers_hand_type(hand in hand(), bid in 1..1000u32) {
+        let line = format!("{hand} {bid}");
+        let camel_card = line.parse::<CamelCard>().unwrap();
+        let joker_card = line.parse::<CamelCardJoker>().unwrap();
+
+        prop_assert!(joker_card.hand_type >= camel_card.hand_type);
+        prop_assert_eq!(joker_card.bid, camel_card.bid);
+    }
+
+    #[test]
+    fn every_hand_gets_its_own_rank(hands in prop::collection::vec(hand(), 1..50)) {
+        // with every bid at 1 the winnings add up every rank exactly once
+        let input = hands
+            .iter()
+            .map(|hand| format!("{hand} 1"))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let ranks = (hands.len() * (hands.len() + 1) / 2) as u32;
+
+        prop_assert_eq!(part1(&input), ranks);
+        prop_assert_eq!(part2(&input), ranks);
+    }
+
+    #[test]
+    fn readers_rank_like_sorting(
+        hands in prop::collection::vec(
+            (
+                // few labels so that identical hands and ties come up often
+                prop::collection::vec(prop::sample::select(vec!['A', 'J', '2']), HAND_SIZE),
+                1..1000u32,
+            ),
+            1..50,
+        )
+    ) {
+        let input = hands
+            .iter()
+            .map(|(hand, bid)| format!("{} {bid}", hand.iter().collect::<String>()))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        prop_assert_eq!(part1_reader(input.as_bytes()).unwrap(), part1(&input));
+        prop_assert_eq!(part2_reader(input.as_bytes()).unwrap(), part2(&input));
+    }
+
+    #[test]
+    fn display_round_trips(seed: u64, size in 1..100usize) {
+        let input = generate(&mut common::Rng::new(seed), size);
+
+        prop_assert_eq!(Day7::parse(&input).unwrap().to_string(), input);
+    }
+}
@@ -0,0 +1,61 @@
+use std::{collections::HashSet, fmt::Write};
+
+use common::Rng;
+
+/// Number of distinct three letter node names
+const MAX_NODES: usize = 26 * 26 * 26;
+
+/// Map of up to 300 instructions and a network of `size` nodes. Both
+/// paths out of every node lead one to three nodes further along a chain
+/// from AAA to ZZZ, so following any instructions from AAA always ends up
+/// at ZZZ, after a number of steps in proportion to `size`
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(2, MAX_NODES);
+
+    let instructions: String = (0..rng.range(1..301))
+        .map(|_| if rng.one_in(2) { 'L' } else { 'R' })
+        .collect();
+
+    let mut names = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut chain = vec!["AAA".to_string()];
+    while chain.len() < size - 1 {
+        let name: String = (0..3)
+            .map(|_| char::from(b'A' + rng.range(0..26) as u8))
+            .collect();
+        if names.insert(name.clone()) {
+            chain.push(name);
+        }
+    }
+    chain.push("ZZZ".to_string());
+
+    let mut lines: Vec<String> = (0..size - 1)
+        .map(|idx| {
+            let left = &chain[(idx + 1 + rng.index(3)).min(size - 1)];
+            let right = &chain[(idx + 1 + rng.index(3)).min(size - 1)];
+            format!("{} = ({left}, {right})", chain[idx])
+        })
+        .collect();
+    lines.push("ZZZ = (ZZZ, ZZZ)".to_string());
+    rng.shuffle(&mut lines);
+
+    let mut input = format!("{instructions}\n\n");
+    for line in lines {
+        writeln!(input, "{line}").unwrap();
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let input = generate(&mut Rng::new(1), 100);
+
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        assert_eq!(input.lines().count(), 102);
+        assert!(crate::try_part1(&input).is_ok());
+    }
+}
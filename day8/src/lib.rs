@@ -1,82 +1,663 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::Display,
+    str::FromStr,
+};
 
-#[derive(Debug)]
-struct HauntedWasteland {
+use common::{Answer, Explanation, Position, Solution, Variant};
+use log::{debug, trace};
+
+mod generate;
+#[cfg(test)]
+mod proptests;
+
+pub use generate::generate;
+
+/// Error from reading the documents about the haunted wasteland, or from
+/// following them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Syntax that the documents are missing
+    Expected {
+        position: Position,
+        expected: &'static str,
+    },
+    /// An instruction other than L or R
+    InvalidInstruction {
+        position: Position,
+        instruction: char,
+    },
+    /// A node that is referenced but never defined in the network
+    UndefinedNode { position: Position, node: String },
+    /// AAA or ZZZ, the ends of the path part 1 follows, missing from the
+    /// network
+    MissingNode { node: &'static str },
+    /// Following the instructions from AAA comes back to where a pass of
+    /// them started without ever reaching ZZZ
+    NoPath,
+}
+
+impl ParseError {
+    /// Where in the documents the error is, `None` for one from following
+    /// them
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            ParseError::Expected { position, .. }
+            | ParseError::InvalidInstruction { position, .. }
+            | ParseError::UndefinedNode { position, .. } => Some(*position),
+            ParseError::MissingNode { .. } | ParseError::NoPath => None,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Expected { position, expected } => {
+                write!(f, "{position}: expected {expected}")
+            }
+            ParseError::InvalidInstruction {
+                position,
+                instruction,
+            } => write!(f, "{position}: expected L or R, found `{instruction}`"),
+            ParseError::UndefinedNode { position, node } => {
+                write!(f, "{position}: node `{node}` is never defined")
+            }
+            ParseError::MissingNode { node } => write!(f, "the network has no node named {node}"),
+            ParseError::NoPath => write!(f, "following the instructions never reaches ZZZ"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Left and right instructions, and the network of nodes they walk
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "HauntedWastelandData", into = "HauntedWastelandData")
+)]
+pub struct HauntedWasteland {
     instructions: Vec<char>,
-    network_map: HashMap<String, (String, String)>,
+    /// Name, left node and right node of each network line, in the order
+    /// the map lists them
+    network: Vec<(String, (String, String))>,
+    /// Where in `network` each node is defined, the last line naming it
+    /// if more than one does
+    index: HashMap<String, usize>,
+}
+
+impl HauntedWasteland {
+    /// Map with `instructions` and `nodes` of name, left node and right
+    /// node
+    ///
+    /// # Panics
+    ///
+    /// If there are no instructions, an instruction isn't `L` or `R`, or a
+    /// node leads to a node that isn't in `nodes`
+    pub fn new<'a>(
+        instructions: &str,
+        nodes: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>,
+    ) -> Self {
+        let network = nodes
+            .into_iter()
+            .map(|(node, left, right)| (node.to_string(), (left.to_string(), right.to_string())))
+            .collect();
+
+        HauntedWasteland::try_new(instructions, network).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_new(
+        instructions: &str,
+        network: Vec<(String, (String, String))>,
+    ) -> Result<Self, String> {
+        if instructions.is_empty() {
+            return Err("expected at least one instruction".to_string());
+        }
+        if let Some(instruction) = instructions.chars().find(|c| *c != 'L' && *c != 'R') {
+            return Err(format!("expected L or R, found `{instruction}`"));
+        }
+
+        let index = index_of(&network);
+        for (_, (left, right)) in &network {
+            if let Some(node) = [left, right]
+                .into_iter()
+                .find(|node| !index.contains_key(*node))
+            {
+                return Err(format!("node `{node}` is never defined"));
+            }
+        }
+
+        Ok(HauntedWasteland {
+            instructions: instructions.chars().collect(),
+            network,
+            index,
+        })
+    }
+
+    /// `L` and `R` instructions, followed in order and then repeated
+    pub fn instructions(&self) -> &[char] {
+        &self.instructions
+    }
+
+    /// Left and right nodes that `node` leads to
+    pub fn node(&self, node: &str) -> Option<(&str, &str)> {
+        self.index.get(node).map(|&idx| {
+            let (left, right) = &self.network[idx].1;
+            (left.as_str(), right.as_str())
+        })
+    }
+
+    /// Name of every node, sorted
+    pub fn nodes(&self) -> Vec<&str> {
+        let mut nodes: Vec<&str> = self.index.keys().map(String::as_str).collect();
+        nodes.sort_unstable();
+        nodes
+    }
+}
+
+/// Text of the map: the instructions, a blank line, then a line for each
+/// node in the order the map lists them
+impl Display for HauntedWasteland {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for instruction in &self.instructions {
+            write!(f, "{instruction}")?;
+        }
+        writeln!(f, "\n")?;
+
+        for (node, (left, right)) in &self.network {
+            writeln!(f, "{node} = ({left}, {right})")?;
+        }
+        Ok(())
+    }
 }
 
 impl FromStr for HauntedWasteland {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (instructions, network) = s.split_once("\n\n").unwrap();
+        let (instructions, network) = s.split_once("\n\n").ok_or_else(|| ParseError::Expected {
+            position: Position::end_of(s, s),
+            expected: "a blank line between the instructions and the network",
+        })?;
+
+        debug!("instructions: {instructions}");
+        trace!("network:\n{network}");
 
-        println!("{}", instructions);
-        println!("{}", network);
+        if instructions.is_empty() {
+            return Err(ParseError::Expected {
+                position: Position::within(s, instructions),
+                expected: "at least one instruction",
+            });
+        }
+
+        if let Some(err) = invalid_instructions(s, instructions).next() {
+            return Err(err);
+        }
 
-        let mut network_map = HashMap::new();
+        let mut nodes = vec![];
+        let mut references = vec![];
 
         for line in network.lines() {
-            let (key, value) = line.split_once(" = (").unwrap();
-            let (left_val, right_val) = value.split_once(", ").unwrap();
-            let right_val = right_val.strip_suffix(')').unwrap();
+            let (key, left_val, right_val) = parse_node(s, line)?;
 
-            network_map.insert(
+            references.push(left_val);
+            references.push(right_val);
+            nodes.push((
                 key.to_string(),
                 (left_val.to_string(), right_val.to_string()),
-            );
+            ));
+        }
+
+        let index = index_of(&nodes);
+        if let Some(node) = references
+            .into_iter()
+            .find(|node| !index.contains_key(*node))
+        {
+            return Err(ParseError::UndefinedNode {
+                position: Position::within(s, node),
+                node: node.to_string(),
+            });
         }
 
         Ok(HauntedWasteland {
             instructions: instructions.chars().collect(),
-            network_map,
+            network: nodes,
+            index,
         })
     }
 }
 
-pub fn part1(input: &str) -> u32 {
-    let haunted_wasteland = input.parse::<HauntedWasteland>().unwrap();
+/// Where in `network` each node is defined, keeping the last line for a
+/// node named more than once
+fn index_of(network: &[(String, (String, String))]) -> HashMap<String, usize> {
+    network
+        .iter()
+        .enumerate()
+        .map(|(idx, (node, _))| (node.clone(), idx))
+        .collect()
+}
+
+/// A map as it's serialized, with the instructions as text and the
+/// network in node order
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HauntedWastelandData {
+    instructions: String,
+    network: std::collections::BTreeMap<String, (String, String)>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<HauntedWastelandData> for HauntedWasteland {
+    type Error = String;
+
+    fn try_from(data: HauntedWastelandData) -> Result<Self, Self::Error> {
+        HauntedWasteland::try_new(&data.instructions, data.network.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<HauntedWasteland> for HauntedWastelandData {
+    fn from(haunted_wasteland: HauntedWasteland) -> Self {
+        HauntedWastelandData {
+            instructions: haunted_wasteland.instructions.iter().collect(),
+            network: haunted_wasteland.network.into_iter().collect(),
+        }
+    }
+}
+
+/// Errors for each instruction in `instructions`, a subslice of `s`,
+/// that isn't L or R
+fn invalid_instructions<'a>(
+    s: &'a str,
+    instructions: &'a str,
+) -> impl Iterator<Item = ParseError> + 'a {
+    instructions
+        .char_indices()
+        .filter(|&(_, instruction)| instruction != 'L' && instruction != 'R')
+        .map(|(idx, instruction)| ParseError::InvalidInstruction {
+            position: Position::within(s, &instructions[idx..]),
+            instruction,
+        })
+}
+
+/// Name, left node and right node of a network line, a subslice of `s`
+fn parse_node<'a>(s: &str, line: &'a str) -> Result<(&'a str, &'a str, &'a str), ParseError> {
+    let (key, value) = line
+        .split_once(" = (")
+        .ok_or_else(|| ParseError::Expected {
+            position: Position::end_of(s, line),
+            expected: "' = (' after the node",
+        })?;
+    let (left_val, right_val) = value.split_once(", ").ok_or_else(|| ParseError::Expected {
+        position: Position::end_of(s, line),
+        expected: "', ' between the left and right nodes",
+    })?;
+    let right_val = right_val
+        .strip_suffix(')')
+        .ok_or_else(|| ParseError::Expected {
+            position: Position::end_of(s, line),
+            expected: "')' after the right node",
+        })?;
 
-    let mut steps: u32 = 0;
-    let mut location = "AAA".to_string();
-    let target_location = "ZZZ".to_string();
+    Ok((key, left_val, right_val))
+}
 
-    for instruction in haunted_wasteland.instructions.iter().cycle() {
-        if *instruction == 'L' {
-            location = haunted_wasteland
-                .network_map
-                .get(&location)
-                .unwrap()
-                .0
-                .clone();
-        } else {
-            location = haunted_wasteland
-                .network_map
-                .get(&location)
-                .unwrap()
-                .1
-                .clone();
+/// Error when the network is missing AAA or ZZZ, the ends of the path
+/// part 1 follows
+fn check_ends(haunted_wasteland: &HauntedWasteland) -> Result<(), ParseError> {
+    for node in ["AAA", "ZZZ"] {
+        if !haunted_wasteland.index.contains_key(node) {
+            return Err(ParseError::MissingNode { node });
         }
-        steps = steps + 1;
+    }
+
+    Ok(())
+}
+
+/// Move from one node to the next along the path, as a step of
+/// explaining part 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Move {
+    pub step: u64,
+    pub node: String,
+    pub instruction: char,
+    pub next: String,
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = HauntedWasteland;
+
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        input.parse::<HauntedWasteland>()
+    }
+
+    fn part1(haunted_wasteland: &Self::Input) -> Result<Answer, Self::Error> {
+        let mut steps: u64 = 0;
+        let mut location = "AAA".to_string();
+        let target_location = "ZZZ".to_string();
 
-        if location.eq(&target_location) {
+        check_ends(haunted_wasteland)?;
+
+        let mut pass_starts = HashSet::new();
+        for (idx, instruction) in haunted_wasteland.instructions.iter().enumerate().cycle() {
+            // starting a pass where one started before repeats it forever
+            if idx == 0 && !pass_starts.insert(location.clone()) {
+                return Err(ParseError::NoPath);
+            }
+
+            if *instruction == 'L' {
+                location = haunted_wasteland.node(&location).unwrap().0.to_string();
+            } else {
+                location = haunted_wasteland.node(&location).unwrap().1.to_string();
+            }
+            steps += 1;
+
+            if location.eq(&target_location) {
+                break;
+            }
+        }
+
+        Ok(steps.into())
+    }
+}
+
+pub fn try_part1(input: &str) -> Result<Answer, ParseError> {
+    Day8::part1(&Day8::parse(input)?)
+}
+
+pub fn part1(input: &str) -> Answer {
+    try_part1(input).unwrap()
+}
+
+/// Part 1 by working out once where a whole pass of the instructions takes
+/// each node, and how many steps in it first reaches ZZZ, then jumping from
+/// AAA a pass at a time
+fn jump_table_part1(input: &str) -> Result<Answer, ParseError> {
+    let haunted_wasteland = Day8::parse(input)?;
+    check_ends(&haunted_wasteland)?;
+
+    let nodes = haunted_wasteland.nodes();
+    let index: HashMap<&str, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(idx, &node)| (node, idx))
+        .collect();
+    let next: Vec<(usize, usize)> = nodes
+        .iter()
+        .map(|&node| {
+            let (left, right) = haunted_wasteland.node(node).unwrap();
+            (index[left], index[right])
+        })
+        .collect();
+    let target = index["ZZZ"];
+
+    let passes: Vec<(usize, Option<u64>)> = (0..nodes.len())
+        .map(|start| {
+            let mut node = start;
+            let mut reached = None;
+            for (step, &instruction) in (1..).zip(&haunted_wasteland.instructions) {
+                node = if instruction == 'L' {
+                    next[node].0
+                } else {
+                    next[node].1
+                };
+                if node == target && reached.is_none() {
+                    reached = Some(step);
+                }
+            }
+            (node, reached)
+        })
+        .collect();
+
+    let pass_len = haunted_wasteland.instructions.len() as u64;
+    let mut node = index["AAA"];
+    let mut steps: u64 = 0;
+    let mut visited = vec![false; nodes.len()];
+
+    loop {
+        if visited[node] {
+            return Err(ParseError::NoPath);
+        }
+        visited[node] = true;
+
+        let (end, reached) = passes[node];
+        if let Some(reached) = reached {
+            return Ok((steps + reached).into());
+        }
+        steps += pass_len;
+        node = end;
+    }
+}
+
+/// Ways of solving each part, for timing against each other
+pub const VARIANTS: &[Variant<ParseError>] = &[
+    Variant::new("stepping", 1, try_part1),
+    Variant::new("jump-table", 1, jump_table_part1),
+];
+
+/// Part 1 along with every move on the path from AAA to ZZZ
+pub fn explain_part1(input: &str) -> Result<Explanation<Move>, ParseError> {
+    let haunted_wasteland = Day8::parse(input)?;
+    check_ends(&haunted_wasteland)?;
+
+    let mut steps = vec![];
+    let mut location = "AAA";
+    // a pass of the instructions from a node it started from before
+    // goes round the same loop again
+    let mut pass_starts = HashSet::new();
+
+    for (step, (idx, &instruction)) in
+        (1..).zip(haunted_wasteland.instructions.iter().enumerate().cycle())
+    {
+        if idx == 0 && !pass_starts.insert(location) {
+            return Err(ParseError::NoPath);
+        }
+
+        let (left, right) = haunted_wasteland.node(location).unwrap();
+        let next = if instruction == 'L' { left } else { right };
+
+        steps.push(Move {
+            step,
+            node: location.to_string(),
+            instruction,
+            next: next.to_string(),
+        });
+        location = next;
+
+        if location == "ZZZ" {
             break;
         }
     }
 
-    steps
+    Ok(Explanation::new(steps.len() as u64, steps))
+}
+
+/// Every error in the map, where parsing stops at the first: each
+/// instruction that isn't L or R, each malformed network line and each
+/// reference to a node that's never defined
+pub fn lint(input: &str) -> Vec<ParseError> {
+    let mut errors = vec![];
+
+    if let Some((instructions, network)) = input.split_once("\n\n") {
+        errors.extend(invalid_instructions(input, instructions));
+
+        let nodes: Vec<(&str, &str, &str)> = network
+            .lines()
+            .filter_map(|line| parse_node(input, line).map_err(|err| errors.push(err)).ok())
+            .collect();
+        let defined: HashSet<&str> = nodes.iter().map(|(node, _, _)| *node).collect();
+
+        for node in nodes.iter().flat_map(|(_, left, right)| [left, right]) {
+            if !defined.contains(node) {
+                errors.push(ParseError::UndefinedNode {
+                    position: Position::within(input, node),
+                    node: node.to_string(),
+                });
+            }
+        }
+    }
+
+    if let Err(err) = Day8::parse(input) {
+        if !errors.contains(&err) {
+            errors.push(err);
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = include_str!("../input.txt");
+    #[test]
+    fn model_test() {
+        let haunted_wasteland = HauntedWasteland::new(
+            "LLR",
+            [
+                ("AAA", "BBB", "BBB"),
+                ("BBB", "AAA", "ZZZ"),
+                ("ZZZ", "ZZZ", "ZZZ"),
+            ],
+        );
+
+        assert_eq!(
+            haunted_wasteland,
+            Day8::parse("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap()
+        );
+        assert_eq!(haunted_wasteland.instructions(), ['L', 'L', 'R']);
+        assert_eq!(haunted_wasteland.node("BBB"), Some(("AAA", "ZZZ")));
+        assert_eq!(haunted_wasteland.node("CCC"), None);
+        assert_eq!(haunted_wasteland.nodes(), ["AAA", "BBB", "ZZZ"]);
+    }
 
     #[test]
-    fn part1_test() {
-        let result = part1(INPUT);
-        assert_eq!(result, 6);
+    #[should_panic(expected = "node `ZZZ` is never defined")]
+    fn undefined_node_test() {
+        HauntedWasteland::new("L", [("AAA", "ZZZ", "AAA")]);
+    }
+
+    #[test]
+    fn lint_test() {
+        let input = "LXR\n\nAAA = (BBB, CCC)\nBBB = BBB, BBB\nCCC = (DDD, ZZZ)\nZZZ = (ZZZ, ZZZ)";
+        let errors: Vec<String> = lint(input).iter().map(ParseError::to_string).collect();
+
+        assert_eq!(
+            errors,
+            [
+                "1:2: expected L or R, found `X`",
+                "4:15: expected ' = (' after the node",
+                "3:8: node `BBB` is never defined",
+                "5:8: node `DDD` is never defined",
+            ]
+        );
+        for example in [
+            include_str!("../examples/example1.txt"),
+            include_str!("../examples/example2.txt"),
+        ] {
+            assert_eq!(lint(example), []);
+        }
+    }
+
+    #[test]
+    fn explain_test() {
+        let explanation = explain_part1(include_str!("../examples/example2.txt")).unwrap();
+        let path: Vec<(&str, char)> = explanation
+            .steps
+            .iter()
+            .map(|step| (step.node.as_str(), step.instruction))
+            .collect();
+
+        assert_eq!(explanation.answer, Answer::from(6));
+        assert_eq!(
+            path,
+            [
+                ("AAA", 'L'),
+                ("BBB", 'L'),
+                ("AAA", 'R'),
+                ("BBB", 'L'),
+                ("AAA", 'L'),
+                ("BBB", 'R'),
+            ]
+        );
+        assert_eq!(explanation.steps[5].next, "ZZZ");
+    }
+
+    #[test]
+    fn jump_table_test() {
+        for input in [
+            include_str!("../examples/example1.txt"),
+            include_str!("../examples/example2.txt"),
+        ] {
+            assert_eq!(jump_table_part1(input), try_part1(input));
+        }
+
+        for seed in 0..20 {
+            let input = generate(&mut common::Rng::new(seed), 200);
+            assert_eq!(jump_table_part1(&input), try_part1(&input), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn no_path_test() {
+        let input = "LL\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)";
+
+        assert_eq!(
+            explain_part1(input).unwrap_err().to_string(),
+            "following the instructions never reaches ZZZ"
+        );
+        assert_eq!(try_part1(input), Err(ParseError::NoPath));
+        assert_eq!(jump_table_part1(input), Err(ParseError::NoPath));
+    }
+
+    #[test]
+    fn display_test() {
+        let input = "LR\n\nZZZ = (ZZZ, ZZZ)\nAAA = (BBB, ZZZ)\nBBB = (AAA, AAA)\n";
+
+        assert_eq!(Day8::parse(input).unwrap().to_string(), input);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let invalid_instruction = "LLX\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)";
+        let undefined_node = "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)";
+        let missing_start = "LLR\n\nBBB = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)";
+
+        assert_eq!(
+            try_part1(invalid_instruction),
+            Err(ParseError::InvalidInstruction {
+                position: Position { line: 1, column: 3 },
+                instruction: 'X',
+            })
+        );
+        assert_eq!(
+            try_part1(undefined_node),
+            Err(ParseError::UndefinedNode {
+                position: Position {
+                    line: 4,
+                    column: 13
+                },
+                node: "ZZZ".to_string(),
+            })
+        );
+        assert_eq!(
+            try_part1(missing_start).unwrap_err().to_string(),
+            "the network has no node named AAA"
+        );
     }
 }
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+
+use super::*;
+
+/// Steps from AAA to ZZZ, reading the network straight from the text
+fn naive_steps(input: &str) -> u32 {
+    let mut lines = input.lines();
+    let instructions: Vec<char> = lines.next().unwrap().chars().collect();
+    let network: HashMap<&str, (&str, &str)> = lines
+        .filter(|line| !line.is_empty())
+        .map(|line| (&line[0..3], (&line[7..10], &line[12..15])))
+        .collect();
+
+    let mut node = "AAA";
+    let mut steps = 0;
+    while node != "ZZZ" {
+        let (left, right) = network[node];
+        node = match instructions[steps % instructions.len()] {
+            'L' => left,
+            _ => right,
+        };
+        steps += 1;
+    }
+
+    steps as u32
+}
+
+proptest! {
+    #[test]
+    fn parse_never_panics(input in "\\PC*") {
+        let _ = Day8::parse(&input);
+    }
+
+    #[test]
+    fn part1_matches_naive_walk(seed: u64, size in 2..200usize) {
+        let input = generate(&mut common::Rng::new(seed), size);
+
+        prop_assert_eq!(part1(&input), naive_steps(&input));
+    }
+
+    #[test]
+    fn display_round_trips(seed: u64, size in 2..200usize) {
+        // generated nodes are shuffled, so this also checks their order is kept
+        let text = generate(&mut common::Rng::new(seed), size);
+
+        prop_assert_eq!(Day8::parse(&text).unwrap().to_string(), text);
+    }
+}
@@ -0,0 +1,243 @@
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fmt::{Display, Write},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use toml::{Table, Value};
+
+/// Manifest in a day's examples directory, mapping each example file to
+/// the answers the puzzle text gives for it:
+///
+/// ```toml
+/// # examples/example1.txt
+/// [example1]
+/// part1 = 2
+/// ```
+pub const MANIFEST: &str = "examples.toml";
+
+/// Example input from a puzzle's text along with its expected answers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// File stem of the example in the examples directory
+    pub name: String,
+    pub path: PathBuf,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    /// Expected answer for a part, `None` when the example doesn't give one
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn input(&self) -> io::Result<String> {
+        fs::read_to_string(&self.path)
+    }
+}
+
+/// Error from reading an examples manifest
+#[derive(Debug)]
+pub enum ManifestError {
+    Io { path: PathBuf, error: io::Error },
+    Invalid { path: PathBuf, message: String },
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManifestError::Io { path, error } => {
+                write!(f, "failed to read {}: {error}", path.display())
+            }
+            ManifestError::Invalid { path, message } => write!(f, "{}: {message}", path.display()),
+        }
+    }
+}
+
+impl Error for ManifestError {}
+
+/// Every example listed in the manifest of `dir`, sorted by name. A
+/// directory without a manifest has no examples.
+pub fn load(dir: &Path) -> Result<Vec<Example>, ManifestError> {
+    let path = dir.join(MANIFEST);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(error) => return Err(ManifestError::Io { path, error }),
+    };
+
+    parse(dir, &text).map_err(|message| ManifestError::Invalid { path, message })
+}
+
+fn parse(dir: &Path, text: &str) -> Result<Vec<Example>, String> {
+    let manifest = text.parse::<Table>().map_err(|err| err.to_string())?;
+
+    let mut test_names = HashMap::new();
+    for name in manifest.keys() {
+        if let Some(other) = test_names.insert(test_name(name), name) {
+            return Err(format!(
+                "`{other}` and `{name}` would both be tested as `example_{}`",
+                test_name(name)
+            ));
+        }
+    }
+
+    manifest
+        .iter()
+        .map(|(name, answers)| {
+            let answers = answers
+                .as_table()
+                .ok_or(format!("expected `{name}` to be a table of answers"))?;
+
+            let mut example = Example {
+                name: name.clone(),
+                path: dir.join(format!("{name}.txt")),
+                part1: None,
+                part2: None,
+            };
+
+            for (part, answer) in answers {
+                let answer = match answer {
+                    Value::Integer(answer) => answer.to_string(),
+                    Value::String(answer) => answer.clone(),
+                    _ => return Err(format!("expected `{name}.{part}` to be a number or string")),
+                };
+
+                match part.as_str() {
+                    "part1" => example.part1 = Some(answer),
+                    "part2" => example.part2 = Some(answer),
+                    _ => {
+                        return Err(format!(
+                            "expected `part1` or `part2` in `{name}`, found `{part}`"
+                        ))
+                    }
+                }
+            }
+
+            Ok(example)
+        })
+        .collect()
+}
+
+/// Name of the tests for the example `name`, after `example_`, with
+/// everything but ASCII letters and digits replaced by `_`
+fn test_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Test functions checking `try_part1`/`try_part2` of the crate against
+/// every answer in the manifest
+pub fn generate_tests(examples: &[Example]) -> String {
+    let mut tests = String::new();
+
+    for example in examples {
+        for part in [1, 2] {
+            let Some(answer) = example.answer(part) else {
+                continue;
+            };
+            // prefixed so that a name starting with a digit is still an
+            // identifier
+            let test_name = test_name(&example.name);
+
+            writeln!(
+                tests,
+                "#[test]\n\
+                 fn example_{test_name}_part{part}() {{\n    \
+                     let answer = crate::try_part{part}(include_str!({path:?})).unwrap();\n    \
+                     assert_eq!(answer.to_string(), {answer:?});\n\
+                 }}\n",
+                path = example.path.display().to_string(),
+            )
+            .expect("writing to a String never fails");
+        }
+    }
+
+    tests
+}
+
+/// For build scripts: write a test per example in the crate's
+/// `examples` directory to `$OUT_DIR/examples.rs`
+pub fn build_tests() {
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").expect("run from a build script");
+    let out_dir = env::var_os("OUT_DIR").expect("run from a build script");
+    let dir = Path::new(&manifest_dir).join("examples");
+
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let examples = load(&dir).unwrap_or_else(|err| panic!("{err}"));
+    for example in &examples {
+        println!("cargo:rerun-if-changed={}", example.path.display());
+    }
+
+    fs::write(
+        Path::new(&out_dir).join("examples.rs"),
+        generate_tests(&examples),
+    )
+    .unwrap_or_else(|err| panic!("failed to write the example tests: {err}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let examples = parse(
+            Path::new("day8/examples"),
+            "[example2]\npart1 = 6\n\n[example1]\npart1 = 2\npart2 = \"6\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "example1");
+        assert_eq!(examples[0].path, Path::new("day8/examples/example1.txt"));
+        assert_eq!(examples[0].answer(1), Some("2"));
+        assert_eq!(examples[0].answer(2), Some("6"));
+        assert_eq!(examples[1].answer(2), None);
+        assert_eq!(
+            parse(Path::new("."), "[example]\npart3 = 1\n"),
+            Err("expected `part1` or `part2` in `example`, found `part3`".to_string())
+        );
+        assert_eq!(
+            parse(
+                Path::new("."),
+                "[two_step]\npart1 = 2\n\n[two-step]\npart1 = 2\n"
+            ),
+            Err("`two-step` and `two_step` would both be tested as `example_two_step`".to_string())
+        );
+    }
+
+    #[test]
+    fn generate_tests_test() {
+        let numbered = Example {
+            name: "1".to_string(),
+            path: PathBuf::from("/day8/examples/1.txt"),
+            part1: None,
+            part2: Some("6".to_string()),
+        };
+        let example = Example {
+            name: "two-step".to_string(),
+            path: PathBuf::from("/day8/examples/two-step.txt"),
+            part1: Some("2".to_string()),
+            part2: None,
+        };
+
+        assert_eq!(
+            generate_tests(&[example]),
+            "#[test]\nfn example_two_step_part1() {\n    \
+                 let answer = crate::try_part1(include_str!(\"/day8/examples/two-step.txt\")).unwrap();\n    \
+                 assert_eq!(answer.to_string(), \"2\");\n}\n\n"
+        );
+        assert!(generate_tests(&[numbered]).contains("fn example_1_part2()"));
+    }
+}
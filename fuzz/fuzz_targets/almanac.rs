@@ -0,0 +1,10 @@
+#![no_main]
+
+use aoc_fuzz::assert_within;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Err(err) = input.parse::<day5::Almanac>() {
+        assert_within(input, err.position());
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc_fuzz::assert_within;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Err(err) = input.parse::<day8::HauntedWasteland>() {
+        let position = err.position().expect("parse errors have a position");
+        assert_within(input, position);
+    }
+});
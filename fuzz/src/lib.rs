@@ -0,0 +1,22 @@
+use common::Position;
+
+/// Check a parse error points somewhere inside `input`, or just past the
+/// end of a line for syntax that is missing
+pub fn assert_within(input: &str, position: Position) {
+    let lines: Vec<&str> = input.split('\n').collect();
+
+    assert!(
+        (1..=lines.len()).contains(&position.line),
+        "line {} outside the {} lines of {input:?}",
+        position.line,
+        lines.len()
+    );
+
+    let line = lines[position.line - 1];
+    assert!(
+        (1..=line.len() + 1).contains(&position.column),
+        "column {} outside line {:?}",
+        position.column,
+        line
+    );
+}
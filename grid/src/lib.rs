@@ -0,0 +1,435 @@
+use std::{
+    convert::Infallible,
+    error::Error,
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// Row and column of a cell in a [`Grid`], both counted from 0 at the
+/// top left
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Self {
+        Pos { row, col }
+    }
+
+    /// Position moved by the given number of rows and columns, `None`
+    /// when it would move above or left of the grid
+    pub fn offset(self, rows: isize, cols: isize) -> Option<Pos> {
+        Some(Pos {
+            row: self.row.checked_add_signed(rows)?,
+            col: self.col.checked_add_signed(cols)?,
+        })
+    }
+}
+
+impl Display for Pos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.row, self.col)
+    }
+}
+
+/// Offsets of the 4 orthogonal neighbours, clockwise starting above
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets of all 8 neighbours, clockwise starting above
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// Error from parsing a grid out of text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError<E> {
+    /// The text has no cells
+    Empty,
+    /// A row that isn't as wide as the first row
+    RowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character that couldn't be turned into a cell
+    Cell { pos: Pos, error: E },
+}
+
+impl<E: Display> Display for ParseGridError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "expected at least one row"),
+            ParseGridError::RowLength {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected row {row} to have {expected} cells, found {found}"
+            ),
+            ParseGridError::Cell { pos, error } => write!(f, "cell {pos}: {error}"),
+        }
+    }
+}
+
+impl<E: Error> Error for ParseGridError<E> {}
+
+/// A rectangular grid of cells stored row by row in a flat `Vec`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Create a grid with every cell set to `value`
+    pub fn new(rows: usize, cols: usize, value: T) -> Self {
+        Grid {
+            cells: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Grid with rows and columns swapped
+    pub fn transpose(&self) -> Grid<T> {
+        let cells = (0..self.cols)
+            .flat_map(|col| self.column(col).cloned())
+            .collect();
+
+        Grid {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Grid turned a quarter turn clockwise
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        let cells = (0..self.cols)
+            .flat_map(|col| self.column(col).rev().cloned())
+            .collect();
+
+        Grid {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Grid turned a quarter turn anticlockwise
+    pub fn rotate_anticlockwise(&self) -> Grid<T> {
+        let cells = (0..self.cols)
+            .rev()
+            .flat_map(|col| self.column(col).cloned())
+            .collect();
+
+        Grid {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from cells laid out row by row, `None` when the
+    /// number of cells doesn't fill the grid exactly
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Option<Self> {
+        (cells.len() == rows * cols).then_some(Grid { cells, rows, cols })
+    }
+
+    /// Parse a grid with one row per line, turning each character into
+    /// a cell with `cell`
+    pub fn parse_with<E>(
+        s: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseGridError<E>> {
+        let mut cells = vec![];
+        let mut rows = 0;
+        let mut cols = 0;
+
+        for (row, line) in s.lines().enumerate() {
+            let found = line.chars().count();
+            if row == 0 {
+                cols = found;
+            } else if found != cols {
+                return Err(ParseGridError::RowLength {
+                    row,
+                    expected: cols,
+                    found,
+                });
+            }
+
+            for (col, c) in line.chars().enumerate() {
+                let value = cell(c).map_err(|error| ParseGridError::Cell {
+                    pos: Pos::new(row, col),
+                    error,
+                })?;
+                cells.push(value);
+            }
+            rows += 1;
+        }
+
+        if cells.is_empty() {
+            return Err(ParseGridError::Empty);
+        }
+
+        Ok(Grid { cells, rows, cols })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn in_bounds(&self, pos: Pos) -> bool {
+        pos.row < self.rows && pos.col < self.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.in_bounds(pos)
+            .then(|| &self.cells[pos.row * self.cols + pos.col])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.in_bounds(pos) {
+            Some(&mut self.cells[pos.row * self.cols + pos.col])
+        } else {
+            None
+        }
+    }
+
+    /// Set a cell, returning `false` when `pos` is outside the grid
+    pub fn set(&mut self, pos: Pos, value: T) -> bool {
+        match self.get_mut(pos) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Every position in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| Pos::new(row, col)))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The cells of a single row
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Every row, top to bottom
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    /// The cells of a single column, top to bottom
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        (0..self.rows).map(move |row| &self.cells[row * self.cols + col])
+    }
+
+    /// Cells in the rectangle between two corners, both inclusive and
+    /// clamped to the grid, row by row
+    pub fn region(&self, top_left: Pos, bottom_right: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        let last_row = bottom_right.row.min(self.rows.saturating_sub(1));
+        let last_col = bottom_right.col.min(self.cols.saturating_sub(1));
+
+        (top_left.row..=last_row)
+            .flat_map(move |row| (top_left.col..=last_col).map(move |col| Pos::new(row, col)))
+            .filter(|&pos| self.in_bounds(pos))
+            .map(|pos| (pos, &self[pos]))
+    }
+
+    /// Orthogonal neighbours inside the grid, clockwise starting above
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &NEIGHBOURS_4)
+    }
+
+    /// Orthogonal and diagonal neighbours inside the grid, clockwise
+    /// starting above
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &NEIGHBOURS_8)
+    }
+
+    fn neighbours(
+        &self,
+        pos: Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + '_ {
+        offsets
+            .iter()
+            .filter_map(move |&(rows, cols)| pos.offset(rows, cols))
+            .filter(|&pos| self.in_bounds(pos))
+    }
+
+    /// Grid with `f` applied to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        assert!(self.in_bounds(pos), "{pos} is outside the grid");
+        &self.cells[pos.row * self.cols + pos.col]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        assert!(self.in_bounds(pos), "{pos} is outside the grid");
+        &mut self.cells[pos.row * self.cols + pos.col]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError<Infallible>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, Ok)
+    }
+}
+
+/// Writes one line per row with no separator between cells, so a
+/// `Grid<char>` prints back the text it was parsed from
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, row) in self.iter_rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef";
+
+    #[test]
+    fn parse_test() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+
+        assert_eq!(grid.rows(), 2);
+        assert_eq!(grid.cols(), 3);
+        assert_eq!(grid[Pos::new(1, 2)], 'f');
+        assert_eq!(grid.to_string(), INPUT);
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(ParseGridError::RowLength {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!("".parse::<Grid<char>>(), Err(ParseGridError::Empty));
+    }
+
+    #[test]
+    fn parse_with_test() {
+        let grid = Grid::parse_with("12\n34", |c| c.to_digit(10).ok_or(c)).unwrap();
+        assert_eq!(grid.row(1), &[3, 4]);
+
+        let err = Grid::parse_with("12\n3x", |c| c.to_digit(10).ok_or(c)).unwrap_err();
+        assert_eq!(
+            err,
+            ParseGridError::Cell {
+                pos: Pos::new(1, 1),
+                error: 'x'
+            }
+        );
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+
+        let corner: Vec<Pos> = grid.neighbours8(Pos::new(0, 0)).collect();
+        assert_eq!(corner, [Pos::new(0, 1), Pos::new(1, 1), Pos::new(1, 0)]);
+
+        let middle: Vec<char> = grid
+            .neighbours4(Pos::new(1, 1))
+            .map(|pos| grid[pos])
+            .collect();
+        assert_eq!(middle, ['b', 'f', 'd']);
+        assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn rows_columns_and_regions_test() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+
+        assert_eq!(grid.row(0), &['a', 'b', 'c']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.iter_rows().count(), 2);
+
+        let region: String = grid
+            .region(Pos::new(0, 1), Pos::new(5, 5))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(region, "bcef");
+    }
+
+    #[test]
+    fn transpose_and_rotate_test() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(
+            grid.rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            grid
+        );
+    }
+
+    #[test]
+    fn get_and_set_test() {
+        let mut grid = Grid::new(2, 2, 0);
+
+        assert!(grid.set(Pos::new(1, 0), 7));
+        assert!(!grid.set(Pos::new(2, 0), 7));
+        assert_eq!(grid.get(Pos::new(1, 0)), Some(&7));
+        assert_eq!(grid.get(Pos::new(0, 2)), None);
+        assert_eq!(grid.map(|cell| cell * 2)[Pos::new(1, 0)], 14);
+        assert_eq!(Grid::from_vec(1, 2, vec![1, 2, 3]), None);
+    }
+}